@@ -0,0 +1,508 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::Args;
+use color_eyre::eyre::{bail, ensure, eyre, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use utils::report::{Report, Status};
+
+use crate::registry::{self, Solution};
+use crate::run::{parse_seconds, run_with_timeout, selected_parts};
+
+/// The history in the root of the workspace, unless another file is given.
+const HISTORY: &str = "perf-history.jsonl";
+
+/// Options for benchmarking solutions.
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// The year to benchmark, the latest year by default.
+    #[arg(long)]
+    pub year: Option<u16>,
+
+    /// The day to benchmark, every day of the year by default.
+    #[arg(long)]
+    pub day: Option<u8>,
+
+    /// The part to benchmark, every part by default.
+    #[arg(long, requires = "day")]
+    pub part: Option<u8>,
+
+    /// How many times to run every part.
+    #[arg(long, default_value_t = 25, value_parser = clap::value_parser!(u32).range(1..))]
+    pub samples: u32,
+
+    /// Give up on a part once a run of it takes longer than this many seconds, and go on with the rest.
+    #[arg(long, value_name = "SECONDS", default_value = "60", value_parser = parse_seconds)]
+    pub timeout: Duration,
+
+    /// The file the results are appended to, `perf-history.jsonl` in the root of the workspace by default.
+    #[arg(long, value_name = "PATH")]
+    pub history: Option<PathBuf>,
+
+    /// The root of the workspace.
+    #[arg(long, default_value = ".")]
+    pub root: PathBuf,
+}
+
+/// Options for showing the performance history.
+#[derive(Debug, Args)]
+pub struct PerfHistoryArgs {
+    /// Only show the given year.
+    #[arg(long)]
+    pub year: Option<u16>,
+
+    /// Only show the given day.
+    #[arg(long)]
+    pub day: Option<u8>,
+
+    /// How many previous runs the latest run is compared with.
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+    pub runs: u32,
+
+    /// How much slower than the previous runs, in percent, the latest run may be before it is a regression.
+    #[arg(long, default_value_t = 10.0)]
+    pub threshold: f64,
+
+    /// The file the results were appended to by `aoc bench`, `perf-history.jsonl` in the root of the workspace by
+    /// default.
+    #[arg(long, value_name = "PATH")]
+    pub history: Option<PathBuf>,
+
+    /// The root of the workspace.
+    #[arg(long, default_value = ".")]
+    pub root: PathBuf,
+}
+
+/// The result of benchmarking a part, as a line of the history.
+///
+/// # Fields
+///
+/// * `timestamp` - When the benchmark was run, in seconds since the Unix epoch, which is shared by every part of a run.
+/// * `commit` - The commit that was benchmarked, with `-dirty` if there were uncommitted changes.
+/// * `year` - The year of the solution.
+/// * `day` - The day of the solution.
+/// * `part` - The part that was benchmarked.
+/// * `median_ns` - The median time to parse the input and solve the part, in nanoseconds.
+/// * `allocations` - The allocations made while solving the part, if they were counted.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub timestamp: u64,
+    pub commit: String,
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub median_ns: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<u64>,
+}
+
+impl Entry {
+    fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+}
+
+/// Benchmark the selected parts, print their median times and append them to the history, unless this is a debug
+/// build, whose times are not comparable with those of release builds.
+///
+/// # Arguments
+///
+/// * `args` - The parts to benchmark and how.
+///
+/// # Returns
+///
+/// * `Result<()>` - Nothing.
+///
+/// # Errors
+///
+/// * `Report` - If no solution is selected, a part fails, times out or panics, or the history cannot be written. The
+///   other parts are still benchmarked and appended after a part that fails or panics, but nothing is benchmarked or
+///   appended after a part times out, since it keeps running in the background and would slow down the others.
+pub fn bench(args: &BenchArgs) -> Result<()> {
+    let year = args.year.unwrap_or_else(registry::latest_year);
+    let solutions = match args.day {
+        Some(day) => vec![registry::find(year, day).ok_or_else(|| eyre!("There is no solution to {year} day {day}!"))?],
+        None => registry::solutions().filter(|solution| solution.year == year).collect(),
+    };
+    ensure!(!solutions.is_empty(), "There are no solutions to {year}!");
+
+    let history = history(args.history.as_deref(), &args.root);
+    if cfg!(debug_assertions) {
+        println!("Warning: this is a debug build, benchmark with `cargo run --release` to record meaningful times.\n");
+    }
+
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let commit = commit(&args.root);
+
+    let mut entries = Vec::new();
+    let mut failed = 0;
+    for solution in solutions {
+        let input = solution.input(&args.root)?;
+        for part in selected_parts(solution, args.part)? {
+            let entry = match bench_part(solution, part, &input, args.samples, args.timeout, timestamp, &commit)? {
+                Ok(entry) => entry,
+                Err(Status::TimedOut) => bail!(
+                    "{solution} part {part} timed out after {:.2?}, so nothing was benchmarked after it or appended!",
+                    args.timeout,
+                ),
+                Err(status) => {
+                    println!("{solution} part {part}: {status}");
+                    failed += 1;
+                    continue;
+                }
+            };
+            let allocations = entry.allocations.map(|allocations| format!(", {allocations} allocations"));
+            println!("{solution} part {part}: {:.2?}{}", entry.median(), allocations.unwrap_or_default());
+
+            entries.push(entry);
+        }
+    }
+
+    if cfg!(debug_assertions) {
+        println!("\nNot appended to {}, since this is a debug build.", history.display());
+    } else {
+        append(&history, &entries)?;
+        println!("\nAppended {} results for {commit} to {}.", entries.len(), history.display());
+    }
+
+    ensure!(failed == 0, "{failed} parts failed or panicked!");
+
+    Ok(())
+}
+
+/// Run a part the given number of times, after a warm-up run, each run under the time budget, or until it is not
+/// solved, which gives its status instead.
+fn bench_part(
+    solution: &'static Solution,
+    part: u8,
+    input: &str,
+    samples: u32,
+    timeout: Duration,
+    timestamp: u64,
+    commit: &str,
+) -> Result<Result<Entry, Status>> {
+    let run = || -> Result<Result<Report, Status>> {
+        let report = run_with_timeout(solution, part, input, timeout)?;
+        match report.parts.first().map(|result| &result.status) {
+            Some(status) if *status != Status::Solved => Ok(Err(status.clone())),
+            _ => Ok(Ok(report)),
+        }
+    };
+
+    let warm_up = match run()? {
+        Ok(report) => report,
+        Err(status) => return Ok(Err(status)),
+    };
+    let allocations = warm_up.parts.first().and_then(|part| part.alloc).map(|alloc| alloc.allocations);
+
+    let mut times = Vec::new();
+    for _ in 0..samples {
+        match run()? {
+            Ok(report) => times.push(report.parts.iter().map(|part| part.parse + part.solve).sum()),
+            Err(status) => return Ok(Err(status)),
+        }
+    }
+
+    Ok(Ok(Entry {
+        timestamp,
+        commit: commit.to_string(),
+        year: solution.year,
+        day: solution.day,
+        part,
+        median_ns: u64::try_from(median(&mut times).as_nanos())?,
+        allocations,
+    }))
+}
+
+/// The median of some times, or zero if there are none.
+fn median(times: &mut [Duration]) -> Duration {
+    times.sort_unstable();
+
+    match times.len() {
+        0 => Duration::ZERO,
+        len if len % 2 == 0 => (times[len / 2 - 1] + times[len / 2]) / 2,
+        len => times[len / 2],
+    }
+}
+
+/// The commit of the workspace, with `-dirty` if there are uncommitted changes, or `unknown` outside of git.
+fn commit(root: &Path) -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let Some(commit) = git(&["rev-parse", "--short=12", "HEAD"]) else {
+        return String::from("unknown");
+    };
+
+    match git(&["status", "--porcelain", "--untracked-files=no"]) {
+        Some(changes) if !changes.is_empty() => format!("{commit}-dirty"),
+        _ => commit,
+    }
+}
+
+/// The history to use, the given file or else the one in the root of the workspace, wherever the runner runs from.
+fn history(history: Option<&Path>, root: &Path) -> PathBuf {
+    history.map_or_else(|| root.join(HISTORY), Path::to_path_buf)
+}
+
+/// Append entries to the history, a JSON object per line.
+fn append(path: &Path, entries: &[Entry]) -> Result<()> {
+    let mut lines = String::new();
+    for entry in entries {
+        lines.push_str(&serde_json::to_string(entry)?);
+        lines.push('\n');
+    }
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .wrap_err_with(|| format!("Failed to append to {}!", path.display()))
+}
+
+/// Read every entry of the history, oldest first.
+fn read_history(path: &Path) -> Result<Vec<Entry>> {
+    let history = fs::read_to_string(path).wrap_err_with(|| format!("Failed to read {}!", path.display()))?;
+
+    history
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).wrap_err_with(|| format!("{}:{}: malformed entry!", path.display(), index + 1))
+        })
+        .collect()
+}
+
+/// How a part performed in its latest run, compared with the runs before it.
+///
+/// # Fields
+///
+/// * `latest` - The latest run of the part.
+/// * `times` - The median times of the previous runs and then the latest one, oldest first.
+/// * `baseline` - The median of the previous runs, if there were any.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trend<'a> {
+    pub latest: &'a Entry,
+    pub times: Vec<Duration>,
+    pub baseline: Option<Duration>,
+}
+
+impl Trend<'_> {
+    /// How much slower the latest run is than the baseline, in percent, negative if it is faster.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<f64>` - The change, if there were previous runs.
+    #[must_use]
+    pub fn change(&self) -> Option<f64> {
+        let baseline = self.baseline?.as_secs_f64().max(f64::EPSILON);
+
+        Some((self.latest.median().as_secs_f64() / baseline - 1.0) * 100.0)
+    }
+}
+
+/// Compare the latest run of every part with the runs before it.
+///
+/// # Arguments
+///
+/// * `entries` - The history, oldest first.
+/// * `runs` - How many previous runs to compare with.
+///
+/// # Returns
+///
+/// * `Vec<Trend>` - A trend per part, ordered by year, day and part.
+#[must_use]
+pub fn trends(entries: &[Entry], runs: usize) -> Vec<Trend<'_>> {
+    let mut parts = BTreeMap::<_, Vec<&Entry>>::new();
+    for entry in entries {
+        parts.entry((entry.year, entry.day, entry.part)).or_default().push(entry);
+    }
+
+    parts
+        .into_values()
+        .filter_map(|history| {
+            let (latest, previous) = history.split_last()?;
+            let previous = &previous[previous.len().saturating_sub(runs)..];
+            let mut times = previous.iter().map(|entry| entry.median()).collect::<Vec<_>>();
+
+            let baseline = (!times.is_empty()).then(|| median(&mut times.clone()));
+            times.push(latest.median());
+
+            Some(Trend { latest, times, baseline })
+        })
+        .collect()
+}
+
+/// Draw times as a line of bars, from the fastest to the slowest.
+fn sparkline(times: &[Duration]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let (Some(min), Some(max)) = (times.iter().min(), times.iter().max()) else {
+        return String::new();
+    };
+    let range = (*max - *min).as_secs_f64().max(f64::EPSILON);
+
+    times
+        .iter()
+        .map(|time| {
+            let level = ((*time - *min).as_secs_f64() / range * 7.0).round();
+            // The level is between 0 and 7, so it is a valid index.
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            BARS[level as usize]
+        })
+        .collect()
+}
+
+/// Show how the latest benchmark of every part compares with the ones before it, and flag the regressions.
+///
+/// # Arguments
+///
+/// * `args` - The parts to show and the regression threshold.
+///
+/// # Returns
+///
+/// * `Result<()>` - Nothing.
+///
+/// # Errors
+///
+/// * `Report` - If the history cannot be read, or any part regressed.
+pub fn perf_history(args: &PerfHistoryArgs) -> Result<()> {
+    let history = history(args.history.as_deref(), &args.root);
+    let entries = read_history(&history)?
+        .into_iter()
+        .filter(|entry| args.year.is_none_or(|year| entry.year == year))
+        .filter(|entry| args.day.is_none_or(|day| entry.day == day))
+        .collect::<Vec<_>>();
+    ensure!(!entries.is_empty(), "There are no benchmarks in {}, run `aoc bench` first!", history.display());
+
+    let runs = usize::try_from(args.runs)?;
+    let mut regressions = 0;
+    for trend in trends(&entries, runs) {
+        let latest = trend.latest;
+        let mut line = format!(
+            "{} day {} part {}  {:<width$}  {:.2?} at {}",
+            latest.year,
+            latest.day,
+            latest.part,
+            sparkline(&trend.times),
+            latest.median(),
+            latest.commit,
+            width = runs + 1,
+        );
+
+        if let (Some(baseline), Some(change)) = (trend.baseline, trend.change()) {
+            let previous = trend.times.len() - 1;
+            let _ = write!(line, "  ({change:+.1}% on {baseline:.2?} over the previous {previous} runs)");
+
+            if change > args.threshold {
+                regressions += 1;
+                line.push_str("  REGRESSION");
+            }
+        }
+
+        println!("{line}");
+    }
+
+    if regressions > 0 {
+        bail!("{regressions} parts are more than {}% slower than in the previous runs!", args.threshold);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: u64, part: u8, micros: u64) -> Entry {
+        Entry {
+            timestamp,
+            commit: format!("commit{timestamp}"),
+            year: 2023,
+            day: 4,
+            part,
+            median_ns: micros * 1_000,
+            allocations: None,
+        }
+    }
+
+    #[test]
+    fn test_median() {
+        let micros = |micros: &[u64]| micros.iter().copied().map(Duration::from_micros).collect::<Vec<_>>();
+
+        assert_eq!(median(&mut micros(&[3, 1, 2])), Duration::from_micros(2));
+        assert_eq!(median(&mut micros(&[4, 1, 3, 2])), Duration::from_nanos(2_500));
+        assert_eq!(median(&mut []), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_trends() {
+        let entries = [
+            entry(1, 2, 900),
+            entry(1, 1, 10),
+            entry(2, 1, 10),
+            entry(2, 2, 100),
+            entry(3, 2, 110),
+            entry(4, 2, 100),
+            entry(5, 2, 150),
+        ];
+
+        let trends = trends(&entries, 3);
+        assert_eq!(trends.len(), 2);
+
+        let (first, second) = (&trends[0], &trends[1]);
+        assert_eq!((first.latest.part, first.change()), (1, Some(0.0)));
+        assert_eq!(second.latest.commit, "commit5");
+        assert_eq!(second.times.len(), 4);
+        assert_eq!(second.baseline, Some(Duration::from_micros(100)));
+        assert!(second.change().is_some_and(|change| (change - 50.0).abs() < 1e-9));
+
+        assert!(self::trends(&entries[..1], 3)[0].change().is_none());
+    }
+
+    #[test]
+    fn test_history() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("aoc-perf-history-{}.jsonl", std::process::id()));
+        let entries = [entry(1, 1, 10), Entry { allocations: Some(12), ..entry(1, 2, 20) }];
+
+        append(&path, &entries[..1])?;
+        append(&path, &entries[1..])?;
+        assert_eq!(read_history(&path)?, entries);
+        assert!(!fs::read_to_string(&path)?.lines().next().is_some_and(|line| line.contains("allocations")));
+
+        fs::write(&path, "{\"timestamp\": 1}\n")?;
+        assert!(read_history(&path).is_err());
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_path() {
+        assert_eq!(history(None, Path::new("../advent")), PathBuf::from("../advent/perf-history.jsonl"));
+        assert_eq!(history(Some(Path::new("runs.jsonl")), Path::new("../advent")), PathBuf::from("runs.jsonl"));
+    }
+
+    #[test]
+    fn test_sparkline() {
+        let micros = |micros: &[u64]| micros.iter().copied().map(Duration::from_micros).collect::<Vec<_>>();
+
+        assert_eq!(sparkline(&micros(&[1, 8, 4])), "▁█▄");
+        assert_eq!(sparkline(&micros(&[5, 5])), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+}
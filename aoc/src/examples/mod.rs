@@ -0,0 +1,403 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Args;
+use color_eyre::eyre::{ensure, eyre, Result, WrapErr};
+
+use crate::{registry, scaffold};
+
+/// Options for extracting the examples of a day.
+#[derive(Debug, Args)]
+pub struct ExamplesArgs {
+    /// The year of the day, the latest year by default.
+    #[arg(long)]
+    pub year: Option<u16>,
+
+    /// The day to extract the examples of.
+    #[arg(long)]
+    pub day: u8,
+
+    /// The puzzle page, saved from the browser once the parts to extract are unlocked.
+    #[arg(long, value_name = "PATH")]
+    pub page: PathBuf,
+
+    /// The root of the workspace.
+    #[arg(long, default_value = ".")]
+    pub root: PathBuf,
+}
+
+/// An example extracted from a puzzle page, with its answer.
+///
+/// # Fields
+///
+/// * `input` - The example input, without the trailing newline.
+/// * `answer` - The answer the puzzle gives for the example.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Extracted {
+    pub input: String,
+    pub answer: String,
+}
+
+/// Extract the examples of a day from its puzzle page, write them to the `examples` module of its crate, and register
+/// them with the day in `aoc/src/registry/y<year>/mod.rs` if it has none yet.
+///
+/// # Arguments
+///
+/// * `args` - The day and its page.
+///
+/// # Returns
+///
+/// * `Result<()>` - Nothing.
+///
+/// # Errors
+///
+/// * `Report` - If the day has no crate, the page has no examples, or a file cannot be read or written.
+pub fn examples(args: &ExamplesArgs) -> Result<()> {
+    let year = args.year.unwrap_or_else(registry::latest_year);
+    let (directory, package) = match registry::find(year, args.day) {
+        Some(solution) => (solution.directory(&args.root), solution.package.to_string()),
+        None => (args.root.join(scaffold::directory(year, args.day)), scaffold::package(year, args.day)),
+    };
+    let lib = directory.join("src/lib.rs");
+    ensure!(lib.exists(), "There is no crate for {year} day {} in {}!", args.day, directory.display());
+
+    let page = fs::read_to_string(&args.page).wrap_err_with(|| format!("Failed to read {}!", args.page.display()))?;
+    let examples = extract(&page)?;
+    let module = module(year, args.day, &examples);
+
+    let path = directory.join("src/examples/mod.rs");
+    if fs::read_to_string(&path).is_ok_and(|existing| existing == module) {
+        println!("{} is up to date.", path.display());
+
+        return Ok(());
+    }
+
+    fs::create_dir_all(directory.join("src/examples"))?;
+    fs::write(&path, module).wrap_err_with(|| format!("Failed to write {}!", path.display()))?;
+
+    let source = fs::read_to_string(&lib)?;
+    if !source.lines().any(|line| line.trim() == "pub mod examples;") {
+        fs::write(&lib, format!("pub mod examples;\n\n{source}"))?;
+    }
+
+    println!("Wrote the examples of {} parts to {}.", examples.len(), path.display());
+
+    let year_path = args.root.join(format!("aoc/src/registry/y{year}/mod.rs"));
+    let year_module = fs::read_to_string(&year_path).unwrap_or_default();
+    match register(&year_module, args.day, &package) {
+        Some(year_module) => {
+            fs::write(&year_path, year_module).wrap_err_with(|| format!("Failed to write {}!", year_path.display()))?;
+            println!("Registered them in {}.", year_path.display());
+        }
+        None if year_module.contains(&format!("examples: &{package}::examples::EXAMPLES,")) => {}
+        None => println!("Register them with `examples: &{package}::examples::EXAMPLES` in {}.", year_path.display()),
+    }
+
+    Ok(())
+}
+
+/// Point the `examples` of a day in the module of its year at the `examples` module of its crate, if the day is
+/// registered without any.
+///
+/// # Arguments
+///
+/// * `module` - The module of the year.
+/// * `day` - The day.
+/// * `package` - The package of the day.
+///
+/// # Returns
+///
+/// * `Option<String>` - The module with the examples registered, or `None` if the day is not registered without any.
+fn register(module: &str, day: u8, package: &str) -> Option<String> {
+    let day_field = format!("        day: {day},\n");
+    let start = module.find(&day_field)?;
+    let end = module[start..].find("\n    },\n").map_or(module.len(), |end| start + end);
+
+    let field = "examples: &[],";
+    let at = start + module[start..end].find(field)?;
+
+    let mut module = module.to_string();
+    module.replace_range(at..at + field.len(), &format!("examples: &{package}::examples::EXAMPLES,"));
+
+    Some(module)
+}
+
+/// Extract the example and its answer of every part from a puzzle page.
+///
+/// The description of every part is an `<article>`. Its example is its first `<pre><code>` block, or the example of
+/// the part before if it has none, and its answer is the last emphasised code, like `<code><em>142</em></code>`.
+///
+/// # Arguments
+///
+/// * `page` - The HTML of the puzzle page.
+///
+/// # Returns
+///
+/// * `Result<Vec<Extracted>>` - The example of every part, in order.
+///
+/// # Errors
+///
+/// * `Report` - If the page has no descriptions, or a part has no example or no answer.
+pub fn extract(page: &str) -> Result<Vec<Extracted>> {
+    let mut examples = Vec::<Extracted>::new();
+
+    for (article, part) in elements(page, "article").into_iter().zip(1..) {
+        let input = match elements(article, "pre").first() {
+            Some(pre) => text(pre).trim_end_matches('\n').to_string(),
+            None => {
+                let previous = examples.last().map(|example| example.input.clone());
+                previous.ok_or_else(|| eyre!("Part {part} has no example!"))?
+            }
+        };
+
+        // Emphasis is nested either way around, and code blocks are not answers.
+        let prose = elements(article, "pre").into_iter().fold(article.to_string(), |prose, pre| prose.replace(pre, ""));
+        let prose = prose.replace("<em><code>", "<code><em>").replace("</code></em>", "</em></code>");
+        let answer = elements(&prose, "code")
+            .into_iter()
+            .filter_map(|code| code.strip_prefix("<em>")?.strip_suffix("</em>"))
+            .next_back()
+            .map(text)
+            .ok_or_else(|| eyre!("Part {part} has no emphasised answer!"))?;
+
+        examples.push(Extracted { input, answer });
+    }
+
+    ensure!(!examples.is_empty(), "The page has no puzzle description, save the page of the puzzle itself!");
+
+    Ok(examples)
+}
+
+/// The contents of every element with the given tag, in order, assuming they are not nested.
+fn elements<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+    let (open, close) = (format!("<{tag}"), format!("</{tag}>"));
+
+    let mut elements = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        // Skip longer tags with the same prefix, like `<pre` and `<prefix`.
+        if !after.starts_with(['>', ' ', '\t', '\n']) {
+            rest = after;
+            continue;
+        }
+
+        let Some(contents) = after.find('>').map(|end| &after[end + 1..]) else {
+            break;
+        };
+        let Some(end) = contents.find(&close) else {
+            break;
+        };
+
+        elements.push(&contents[..end]);
+        rest = &contents[end + close.len()..];
+    }
+
+    elements
+}
+
+/// The text of some HTML, without its tags and with its character references decoded.
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(index) = rest.find(['<', '&']) {
+        text.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        if rest.starts_with('<') {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            continue;
+        }
+
+        let reference = rest.find(';').map(|end| (&rest[1..end], end));
+        let decoded = reference.and_then(|(name, end)| {
+            let character = match name {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => {
+                    let code = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X"));
+                    let code = match code {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => name.strip_prefix('#').and_then(|decimal| decimal.parse().ok()),
+                    };
+                    char::from_u32(code?)?
+                }
+            };
+
+            Some((character, end))
+        });
+
+        match decoded {
+            Some((character, end)) => {
+                text.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                text.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+
+    text
+}
+
+/// Write some text as a Rust string literal, continuing it on the next line if it has several lines.
+fn literal(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('"', "\\\"");
+
+    // A line continuation would also skip the leading whitespace of the first line.
+    if text.contains('\n') && !text.starts_with(char::is_whitespace) {
+        format!("\"\\\n{escaped}\"")
+    } else {
+        format!("\"{escaped}\"")
+    }
+}
+
+/// Write the `examples` module of a day.
+fn module(year: u16, day: u8, examples: &[Extracted]) -> String {
+    let mut module = format!(
+        "//! The examples of {year} day {day} and their answers, from the puzzle page.\n\
+         //! Regenerate them with `aoc examples --year {year} --day {day} --page <page>` rather than editing them.\n\
+         \n\
+         use utils::example::Example;\n",
+    );
+
+    for (index, example) in examples.iter().enumerate() {
+        let part = index + 1;
+        let answer = literal(&example.answer);
+
+        let _ = match examples[..index].iter().position(|earlier| earlier.input == example.input) {
+            Some(earlier) => writeln!(
+                module,
+                "\n/// The example of part {part}, the same as part {0}.\n\
+                 pub const PART_{part}: Example = Example {{ input: PART_{0}.input, answer: {answer} }};",
+                earlier + 1,
+            ),
+            None => writeln!(
+                module,
+                "\n/// The example of part {part}.\n\
+                 pub const PART_{part}: Example = Example {{\n    input: {},\n    answer: {answer},\n}};",
+                literal(&example.input),
+            ),
+        };
+    }
+
+    let parts = (1..=examples.len()).map(|part| format!("PART_{part}")).collect::<Vec<_>>();
+    let _ = writeln!(
+        module,
+        "\n/// The examples of every part, in order.\npub const EXAMPLES: [Example; {}] = [{}];",
+        examples.len(),
+        parts.join(", "),
+    );
+
+    module
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<head><title>Day 4 - Advent of Code 2023</title></head>
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 4: Scratchcards ---</h2>
+<p>For example:</p>
+<pre><code>Card 1: 41 48 | 83 86
+Card 2: 13 32 | 61 &lt;30&gt;
+</code></pre>
+<p>In the above example, card 1 has <em>four</em> winning numbers (<code>48</code> and <code>83</code>),
+so it is worth <code><em>8</em></code> points.</p>
+<p>So, in this example, the Elf's pile of scratchcards is worth <code><em>13</em></code> points.</p>
+</article>
+<p>Your puzzle answer was <code>26443</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Once all of the originals and copies have been processed, you end up with <em><code>30</code></em> scratchcards.</p>
+</article>
+<p>Your puzzle answer was <code>6284877</code>.</p>
+</main>
+</body>
+</html>
+"#;
+
+    #[test]
+    fn test_extract() -> Result<()> {
+        let examples = extract(PAGE)?;
+        let input = "Card 1: 41 48 | 83 86\nCard 2: 13 32 | 61 <30>";
+
+        assert_eq!(
+            examples,
+            vec![
+                Extracted { input: input.to_string(), answer: String::from("13") },
+                Extracted { input: input.to_string(), answer: String::from("30") },
+            ],
+        );
+
+        assert!(extract("<html><body>Puzzle inputs differ by user.</body></html>").is_err());
+        assert!(extract("<article><pre><code>1</code></pre><p>It is <em>one</em>.</p></article>").is_err());
+        assert!(extract("<article><p>It is <code><em>1</em></code>.</p></article>").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(text("a <em>b</em> &lt;c&gt; &amp;&#39;&#x41;&quot; &nbsp;"), "a b <c> &'A\" &nbsp;");
+        let html = "<pre><code>1</code></pre><prefix>2</prefix><pre class=\"x\">3</pre>";
+        assert_eq!(elements(html, "pre"), ["<code>1</code>", "3"]);
+    }
+
+    #[test]
+    fn test_register() {
+        let entry = |day: u8, examples: &str| {
+            format!("    Solution {{\n        day: {day},\n        examples: {examples},\n    }},\n")
+        };
+        let module = format!("{}{}", entry(5, "&[]"), entry(6, "&[]"));
+
+        assert_eq!(
+            register(&module, 6, "y2023_day_06"),
+            Some(format!("{}{}", entry(5, "&[]"), entry(6, "&y2023_day_06::examples::EXAMPLES"))),
+        );
+        assert_eq!(register(&entry(5, "&y2023_day_05::examples::EXAMPLES"), 5, "y2023_day_05"), None);
+        assert_eq!(register(&module, 7, "y2023_day_07"), None);
+    }
+
+    #[test]
+    fn test_module() -> Result<()> {
+        let examples = [
+            Extracted { input: String::from("1abc2\ntreb7uchet"), answer: String::from("142") },
+            Extracted { input: String::from("  \"two\"\\1nine"), answer: String::from("29") },
+            Extracted { input: String::from("1abc2\ntreb7uchet"), answer: String::from("142") },
+        ];
+
+        assert_eq!(
+            module(2023, 1, &examples),
+            "//! The examples of 2023 day 1 and their answers, from the puzzle page.\n\
+             //! Regenerate them with `aoc examples --year 2023 --day 1 --page <page>` rather than editing them.\n\
+             \n\
+             use utils::example::Example;\n\
+             \n\
+             /// The example of part 1.\n\
+             pub const PART_1: Example = Example {\n    input: \"\\\n1abc2\ntreb7uchet\",\n    answer: \"142\",\n};\n\
+             \n\
+             /// The example of part 2.\n\
+             pub const PART_2: Example = Example {\n    input: \"  \\\"two\\\"\\\\1nine\",\n    answer: \"29\",\n};\n\
+             \n\
+             /// The example of part 3, the same as part 1.\n\
+             pub const PART_3: Example = Example { input: PART_1.input, answer: \"142\" };\n\
+             \n\
+             /// The examples of every part, in order.\n\
+             pub const EXAMPLES: [Example; 3] = [PART_1, PART_2, PART_3];\n",
+        );
+
+        Ok(())
+    }
+}
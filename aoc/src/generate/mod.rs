@@ -0,0 +1,451 @@
+use std::fmt::Write;
+
+use clap::{Args, Subcommand};
+use color_eyre::eyre::{ensure, Result};
+
+/// The spelled-out digits, which Day 1 part 2 also counts as digits.
+const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// Pairs of number words that share letters, like `twone`.
+const OVERLAPS: [&str; 8] = ["oneight", "twone", "threeight", "fiveight", "sevenine", "eightwo", "eighthree", "nineight"];
+
+/// The colours of the cubes in Day 2, starting with the three of the puzzle.
+const COLOURS: [&str; 12] = [
+    "red", "green", "blue", "yellow", "cyan", "magenta", "black", "white", "orange", "purple", "pink", "brown",
+];
+
+/// The symbols of a Day 3 schematic, with `*` first so that gears are common.
+const SYMBOLS: [u8; 10] = *b"**#+$/@%=&";
+
+/// Options for generating a random puzzle input.
+#[derive(Debug, Args)]
+pub struct GenerateArgs {
+    /// The seed of the random generator, so that the same options always generate the same input.
+    #[arg(long, default_value_t = 2023, global = true)]
+    pub seed: u64,
+
+    /// The day to generate an input for, and the size and shape of the input.
+    #[command(subcommand)]
+    pub day: Shape,
+}
+
+/// The size and shape of the input of every day.
+#[derive(Debug, Clone, Copy, Subcommand)]
+pub enum Shape {
+    /// Calibration lines of digits, number words and noise.
+    Day1 {
+        /// The number of lines.
+        #[arg(long, default_value_t = 1000)]
+        lines: usize,
+        /// The length of every line, in characters (roughly, words are never cut off).
+        #[arg(long, default_value_t = 40, value_parser = parse_positive)]
+        length: usize,
+        /// The chance that a piece of a line is a number word instead of a letter of noise.
+        #[arg(long, default_value_t = 0.2, value_parser = parse_chance)]
+        words: f64,
+        /// The chance that a number word overlaps with the next one, like `twone`.
+        #[arg(long, default_value_t = 0.3, value_parser = parse_chance)]
+        overlaps: f64,
+    },
+    /// Games of cubes drawn from a bag.
+    Day2 {
+        /// The number of games.
+        #[arg(long, default_value_t = 100)]
+        games: usize,
+        /// The maximum number of rounds of every game.
+        #[arg(long, default_value_t = 6)]
+        rounds: usize,
+        /// The number of colours of cubes (at most 12).
+        #[arg(long, default_value_t = 3)]
+        colours: usize,
+        /// The maximum number of cubes of a colour in a round.
+        #[arg(long, default_value_t = 20)]
+        max_count: u32,
+    },
+    /// An engine schematic of numbers and symbols.
+    Day3 {
+        /// The width of the schematic.
+        #[arg(long, default_value_t = 140, value_parser = parse_positive)]
+        width: usize,
+        /// The height of the schematic.
+        #[arg(long, default_value_t = 140, value_parser = parse_positive)]
+        height: usize,
+        /// The chance that a number starts at a free cell, from sparse (0) to dense (1).
+        #[arg(long, default_value_t = 0.15, value_parser = parse_chance)]
+        density: f64,
+        /// The chance that a free cell is a symbol.
+        #[arg(long, default_value_t = 0.05, value_parser = parse_chance)]
+        symbols: f64,
+    },
+    /// Scratchcards with winning and actual numbers.
+    Day4 {
+        /// The number of cards.
+        #[arg(long, default_value_t = 200)]
+        cards: usize,
+        /// The number of winning numbers on every card.
+        #[arg(long, default_value_t = 10)]
+        winning: usize,
+        /// The number of actual numbers on every card.
+        #[arg(long, default_value_t = 25)]
+        numbers: usize,
+        /// The chance that a card matches all of its winning numbers, which makes long win chains.
+        ///
+        /// The number of copies grows exponentially with the length of the chains, so keep this low for
+        /// large inputs.
+        #[arg(long, default_value_t = 0.05, value_parser = parse_chance)]
+        chain: f64,
+    },
+}
+
+/// Generate a random puzzle input.
+///
+/// # Arguments
+///
+/// * `args` - The seed, the day and the shape of the input.
+///
+/// # Returns
+///
+/// * `Result<String>` - The input, with a trailing newline.
+///
+/// # Errors
+///
+/// * `Report` - If the shape cannot produce a valid input, e.g. more than 99 numbers on a card.
+pub fn generate(args: &GenerateArgs) -> Result<String> {
+    let mut rng = Rng::new(args.seed);
+
+    match args.day {
+        Shape::Day1 {
+            lines,
+            length,
+            words,
+            overlaps,
+        } => Ok(calibration(&mut rng, lines, length, words, overlaps)),
+        Shape::Day2 {
+            games,
+            rounds,
+            colours,
+            max_count,
+        } => {
+            ensure!((1..=COLOURS.len()).contains(&colours), "There must be 1 to {} colours!", COLOURS.len());
+            ensure!(rounds > 0 && max_count > 0, "Every game needs at least one round with one cube!");
+
+            Ok(cube_games(&mut rng, games, rounds, colours, max_count))
+        }
+        Shape::Day3 {
+            width,
+            height,
+            density,
+            symbols,
+        } => Ok(schematic(&mut rng, width, height, density, symbols)),
+        Shape::Day4 {
+            cards,
+            winning,
+            numbers,
+            chain,
+        } => {
+            ensure!(
+                winning.checked_add(numbers).is_some_and(|total| total <= 99),
+                "A card can only hold 99 distinct numbers!",
+            );
+
+            Ok(scratchcards(&mut rng, cards, winning, numbers, chain))
+        }
+    }
+}
+
+/// Parse a positive number, like the width of a schematic.
+fn parse_positive(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|&value| value > 0)
+        .ok_or_else(|| format!("`{value}` is not a positive number"))
+}
+
+/// Parse a chance, from `0` (never) to `1` (always).
+fn parse_chance(value: &str) -> Result<f64, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|chance| (0.0..=1.0).contains(chance))
+        .ok_or_else(|| format!("`{value}` is not a chance from 0 to 1"))
+}
+
+fn calibration(rng: &mut Rng, lines: usize, length: usize, words: f64, overlaps: f64) -> String {
+    let mut input = String::new();
+
+    for _ in 0..lines {
+        let mut line = String::new();
+        while line.len() < length {
+            if rng.chance(words) {
+                let words: &[&str] = if rng.chance(overlaps) { &OVERLAPS } else { &WORDS };
+                line.push_str(rng.pick::<&str>(words));
+            } else {
+                line.push(char::from(b'a' + u8::try_from(rng.below(26)).unwrap_or_default()));
+            }
+        }
+
+        // Make sure every line has a digit, like in the puzzle.
+        let digit = char::from(b'1' + u8::try_from(rng.below(9)).unwrap_or_default());
+        let position = rng.below(line.len() + 1);
+        line.insert(position, digit);
+
+        input.push_str(&line);
+        input.push('\n');
+    }
+
+    input
+}
+
+fn cube_games(rng: &mut Rng, games: usize, rounds: usize, colours: usize, max_count: u32) -> String {
+    let mut input = String::new();
+
+    for id in 1..=games {
+        let _ = write!(input, "Game {id}:");
+
+        for round in 0..rng.range(1, rounds) {
+            let mut palette = COLOURS[..colours].to_vec();
+            rng.shuffle(&mut palette);
+
+            let drawn = rng.range(1, colours);
+            for (cube, colour) in palette[..drawn].iter().enumerate() {
+                let separator = match (round, cube) {
+                    (0, 0) => " ",
+                    (_, 0) => "; ",
+                    _ => ", ",
+                };
+                let count = rng.range(1, usize::try_from(max_count).unwrap_or(usize::MAX));
+                let _ = write!(input, "{separator}{count} {colour}");
+            }
+        }
+        input.push('\n');
+    }
+
+    input
+}
+
+fn schematic(rng: &mut Rng, width: usize, height: usize, density: f64, symbols: f64) -> String {
+    let mut input = String::new();
+
+    for _ in 0..height {
+        let mut row = Vec::with_capacity(width);
+        while row.len() < width {
+            let digits = rng.range(1, 3);
+
+            // A number has to be followed by something else, or it would run into the next one.
+            if row.len() + digits < width && rng.chance(density) {
+                row.push(b'1' + u8::try_from(rng.below(9)).unwrap_or_default());
+                row.extend((1..digits).map(|_| b'0' + u8::try_from(rng.below(10)).unwrap_or_default()));
+            }
+            row.push(if rng.chance(symbols) { *rng.pick(&SYMBOLS) } else { b'.' });
+        }
+
+        input.extend(row.iter().map(|&byte| char::from(byte)));
+        input.push('\n');
+    }
+
+    input
+}
+
+fn scratchcards(rng: &mut Rng, cards: usize, winning: usize, numbers: usize, chain: f64) -> String {
+    let mut input = String::new();
+    let width = cards.to_string().len();
+
+    for id in 1..=cards {
+        let mut pool = (1..=99).collect::<Vec<u8>>();
+        rng.shuffle(&mut pool);
+        let (winning_numbers, others) = pool.split_at(winning);
+
+        // A card may not win copies of cards past the end of the table.
+        let most = winning.min(numbers).min(cards - id);
+        let matches = if rng.chance(chain) { most } else { rng.range(0, most.min(3)) };
+
+        let mut actual_numbers = winning_numbers[..matches].to_vec();
+        actual_numbers.extend_from_slice(&others[..numbers - matches]);
+        rng.shuffle(&mut actual_numbers);
+
+        let join = |numbers: &[u8]| numbers.iter().map(|number| format!("{number:>2}")).collect::<Vec<_>>().join(" ");
+        let _ = writeln!(input, "Card {id:>width$}: {} | {}", join(winning_numbers), join(&actual_numbers));
+    }
+
+    input
+}
+
+/// A small, seeded random number generator (`SplitMix64`).
+///
+/// This is not a dependency, so that a seed keeps generating the same input when dependencies are updated.
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        z ^ (z >> 31)
+    }
+
+    /// Get a number in `0..n`, or 0 if `n` is 0.
+    fn below(&mut self, n: usize) -> usize {
+        let n = n as u128;
+
+        usize::try_from((u128::from(self.next_u64()) * n) >> 64).unwrap_or_default()
+    }
+
+    /// Get a number in `low..=high`.
+    fn range(&mut self, low: usize, high: usize) -> usize {
+        low + self.below(high.saturating_sub(low).saturating_add(1))
+    }
+
+    /// Return `true` with the given probability.
+    #[allow(clippy::cast_precision_loss)]
+    fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64) < probability
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Shuffle the items in place (Fisher-Yates).
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_with(day: Shape, seed: u64) -> Result<String> {
+        generate(&GenerateArgs { seed, day })
+    }
+
+    #[test]
+    fn test_seeded() -> Result<()> {
+        let shape = Shape::Day1 {
+            lines: 10,
+            length: 30,
+            words: 0.5,
+            overlaps: 0.5,
+        };
+
+        assert_eq!(generate_with(shape, 1)?, generate_with(shape, 1)?);
+        assert_ne!(generate_with(shape, 1)?, generate_with(shape, 2)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_day_1() -> Result<()> {
+        let shape = Shape::Day1 {
+            lines: 50,
+            length: 60,
+            words: 0.8,
+            overlaps: 1.0,
+        };
+        let input = generate_with(shape, 7)?;
+
+        assert_eq!(input.lines().count(), 50);
+        assert!(input.lines().all(|line| line.len() >= 60 && line.bytes().any(|byte| byte.is_ascii_digit())));
+        assert!(day_1::part_2::calibrate_lines(&input) > 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_day_2() -> Result<()> {
+        let shape = Shape::Day2 {
+            games: 30,
+            rounds: 8,
+            colours: 5,
+            max_count: 15,
+        };
+        let games = day_2::part_1::parse_games(&generate_with(shape, 7)?)?;
+
+        assert_eq!(games.len(), 30);
+        assert!(games.iter().all(|(_, rounds)| (1..=8).contains(&rounds.len())));
+        assert!(games.iter().flat_map(|(_, rounds)| rounds).all(|round| round.values().all(|&count| count <= 15)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_day_3() -> Result<()> {
+        let dense = Shape::Day3 {
+            width: 40,
+            height: 30,
+            density: 0.9,
+            symbols: 0.3,
+        };
+        let sparse = Shape::Day3 {
+            width: 40,
+            height: 30,
+            density: 0.05,
+            symbols: 0.3,
+        };
+
+        let dense = generate_with(dense, 7)?.parse::<day_3::Schematic>()?;
+        let sparse = generate_with(sparse, 7)?.parse::<day_3::Schematic>()?;
+
+        assert_eq!((dense.width(), dense.height()), (40, 30));
+        assert!(dense.numbers().len() > 3 * sparse.numbers().len());
+        assert!(dense.numbers().iter().all(|number| number.value > 0 && number.value < 1000));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_day_4() -> Result<()> {
+        let shape = Shape::Day4 {
+            cards: 40,
+            winning: 5,
+            numbers: 8,
+            chain: 0.2,
+        };
+        let input = generate_with(shape, 7)?;
+
+        assert!(day_4::validate(&input).is_empty());
+
+        let cards = input.lines().map(day_4::Card::try_from).collect::<Result<Vec<_>, _>>()?;
+        let copies = day_4::count_copies(&cards, day_4::OverflowPolicy::Error)?;
+        assert!(copies >= 40);
+
+        let too_many = Shape::Day4 {
+            cards: 1,
+            winning: 50,
+            numbers: 50,
+            chain: 0.0,
+        };
+        assert!(generate_with(too_many, 7).is_err());
+
+        let overflowing = Shape::Day4 {
+            cards: 1,
+            winning: usize::MAX,
+            numbers: 1,
+            chain: 0.0,
+        };
+        assert!(generate_with(overflowing, 7).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_positive("140"), Ok(140));
+        assert!(parse_positive("0").is_err() && parse_positive("-1").is_err() && parse_positive("wide").is_err());
+
+        assert_eq!(parse_chance("0.15"), Ok(0.15));
+        assert_eq!((parse_chance("0"), parse_chance("1")), (Ok(0.0), Ok(1.0)));
+        assert!(parse_chance("1.5").is_err() && parse_chance("-0.1").is_err() && parse_chance("NaN").is_err());
+    }
+}
@@ -0,0 +1,351 @@
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use color_eyre::eyre::{ensure, Result, WrapErr};
+use utils::report::{PartReport, Report, Status};
+
+use crate::registry::Solution;
+use crate::run::{describe, read_answers, run_parts, selected_parts};
+
+/// The suffix of the file holding the expected answers of an input, e.g. `alice.answers.txt` for `alice.txt`.
+const SIDECAR: &str = ".answers";
+
+/// How a solution did on one of several inputs.
+///
+/// # Fields
+///
+/// * `name` - The file name of the input.
+/// * `result` - The answers and timings, or why the input could not be solved.
+/// * `expected` - The expected answer to every part, if they were checked.
+#[derive(Debug)]
+pub struct Outcome {
+    pub name: String,
+    pub result: Result<Report, String>,
+    pub expected: Option<BTreeMap<u8, String>>,
+}
+
+impl Outcome {
+    /// Count the parts that went wrong: the wrong answers, the parts that timed out or panicked, the parts without an
+    /// expected answer when checking, or every part if the input could not be solved at all.
+    fn problems(&self, parts: &[u8]) -> usize {
+        let Ok(report) = &self.result else {
+            return parts.len();
+        };
+
+        let expected = |part: u8| self.expected.as_ref().and_then(|expected| expected.get(&part));
+        report
+            .parts
+            .iter()
+            .filter(|part| {
+                let wrong = expected(part.part).is_none_or(|expected| *expected != part.answer);
+                !part.is_solved() || (self.expected.is_some() && wrong)
+            })
+            .count()
+    }
+}
+
+/// Run a solution on every input in a directory and print their answers and timings side by side.
+///
+/// # Arguments
+///
+/// * `solution` - The solution to run.
+/// * `part` - The part to run, or `None` for every part.
+/// * `directory` - The directory of inputs. Files named like `alice.answers.txt` hold the expected answers to
+///   `alice.txt`, in the format of `answers.txt`, and are not inputs themselves.
+/// * `check` - Whether to check the answers against the expected ones, in which case a part without an expected answer,
+///   for instance because the input has no expected answers file, is a problem too.
+/// * `timeout` - How long each part may run.
+///
+/// # Returns
+///
+/// * `Result<usize>` - How many parts went wrong, see [`Outcome`].
+///
+/// # Errors
+///
+/// * `Report` - If the directory has no inputs, or an expected answers file is malformed.
+pub fn run_inputs(
+    solution: &'static Solution,
+    part: Option<u8>,
+    directory: &Path,
+    check: bool,
+    timeout: Duration,
+) -> Result<usize> {
+    let paths = inputs(directory)?;
+    ensure!(!paths.is_empty(), "There are no inputs in {}!", directory.display());
+
+    let parts = selected_parts(solution, part)?;
+
+    let mut outcomes = Vec::new();
+    for path in paths {
+        let expected = if check { Some(read_answers(&sidecar(&path))?) } else { None };
+        let result = utils::error::read_input(Some(&path), b"")
+            .map_err(color_eyre::Report::from)
+            .and_then(|input| run_parts(solution, part, &input, Some(timeout)))
+            .map_err(|report| describe(&report));
+
+        outcomes.push(Outcome { name: name(&path), result, expected });
+    }
+
+    print!("{}", table(&parts, &outcomes));
+
+    for outcome in &outcomes {
+        match &outcome.result {
+            Ok(report) => {
+                for part in &report.parts {
+                    // The table only says that a part failed, so tell why, whether or not the answers are checked.
+                    if let Status::Failed(error) = &part.status {
+                        println!("{} part {}: failed: {error}", outcome.name, part.part);
+                        continue;
+                    }
+
+                    let Some(expected) = &outcome.expected else {
+                        continue;
+                    };
+
+                    match expected.get(&part.part) {
+                        Some(expected) if *expected != part.answer && part.is_solved() => {
+                            println!("{} part {}: expected {expected}", outcome.name, part.part);
+                        }
+                        Some(_) => {}
+                        None => {
+                            let sidecar = name(&sidecar(Path::new(&outcome.name)));
+                            println!("{} part {}: no expected answer in {sidecar}", outcome.name, part.part);
+                        }
+                    }
+                }
+            }
+            Err(error) => println!("{}: {error}", outcome.name),
+        }
+    }
+
+    Ok(outcomes.iter().map(|outcome| outcome.problems(&parts)).sum())
+}
+
+/// List the inputs in a directory, ordered by name, without the expected answers files and hidden files.
+///
+/// # Arguments
+///
+/// * `directory` - The directory of inputs.
+///
+/// # Returns
+///
+/// * `Result<Vec<PathBuf>>` - The inputs.
+///
+/// # Errors
+///
+/// * `Report` - If the directory cannot be read.
+pub fn inputs(directory: &Path) -> Result<Vec<PathBuf>> {
+    let entries = fs::read_dir(directory).wrap_err_with(|| format!("Failed to read {}!", directory.display()))?;
+
+    let mut inputs = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let name = name(&path);
+        let is_hidden = name.starts_with('.');
+
+        if path.is_file() && !is_hidden && !is_sidecar(&name) {
+            inputs.push(path);
+        }
+    }
+    inputs.sort_unstable();
+
+    Ok(inputs)
+}
+
+/// The file holding the expected answers to an input, e.g. `alice.answers.txt` for `alice.txt` or `alice.answers`
+/// for `alice`.
+///
+/// # Arguments
+///
+/// * `input` - The input.
+///
+/// # Returns
+///
+/// * `PathBuf` - The expected answers file, which may not exist.
+#[must_use]
+pub fn sidecar(input: &Path) -> PathBuf {
+    let mut name = input.file_stem().map(OsString::from).unwrap_or_default();
+    name.push(SIDECAR);
+    if let Some(extension) = input.extension() {
+        name.push(".");
+        name.push(extension);
+    }
+
+    input.with_file_name(name)
+}
+
+/// Whether a file is an expected answers file by its name, like `alice.answers.txt` or `bob.answers`, see [`sidecar`].
+fn is_sidecar(name: &str) -> bool {
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    name.ends_with(SIDECAR) || stem.ends_with(SIDECAR)
+}
+
+fn name(path: &Path) -> String {
+    path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().into_owned())
+}
+
+/// Tabulate the answer and the time of every part on every input, an input per row.
+fn table(parts: &[u8], outcomes: &[Outcome]) -> String {
+    let mut rows = vec![std::iter::once(String::from("Input"))
+        .chain(parts.iter().flat_map(|part| [format!("Part {part}"), String::from("Time")]))
+        .collect::<Vec<_>>()];
+
+    for outcome in outcomes {
+        let mut row = vec![outcome.name.clone()];
+
+        for part in parts {
+            let (answer, time) = match &outcome.result {
+                Ok(report) => match report.parts.iter().find(|result| result.part == *part) {
+                    Some(result @ PartReport { status: Status::TimedOut | Status::Panicked, .. }) => {
+                        (result.status.to_string(), format!("{:.2?}", result.solve))
+                    }
+                    Some(PartReport { status: Status::Failed(_), .. }) => (String::from("failed"), String::from("-")),
+                    Some(result) => {
+                        let expected = outcome.expected.as_ref().map(|expected| expected.get(part));
+                        let check = match expected {
+                            Some(Some(expected)) if *expected == result.answer => " ✓",
+                            Some(Some(_)) => " ✗",
+                            Some(None) => " ?",
+                            None => "",
+                        };
+
+                        (format!("{}{check}", result.answer), format!("{:.2?}", result.parse + result.solve))
+                    }
+                    None => (String::from("-"), String::from("-")),
+                },
+                Err(_) => (String::from("failed"), String::from("-")),
+            };
+            row.extend([answer, time]);
+        }
+
+        rows.push(row);
+    }
+
+    let columns = rows[0].len();
+    let widths = (0..columns)
+        .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+        .collect::<Vec<_>>();
+
+    let mut table = String::new();
+    for row in rows {
+        for (column, (cell, width)) in row.iter().zip(&widths).enumerate() {
+            // The names of the inputs are aligned left, the answers and timings right.
+            let _ = match column {
+                0 => write!(table, "{cell:<width$}"),
+                _ => write!(table, "  {cell:>width$}"),
+            };
+        }
+        table.push('\n');
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::eyre;
+
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_sidecar() {
+        assert_eq!(sidecar(Path::new("inputs/alice.txt")), PathBuf::from("inputs/alice.answers.txt"));
+        assert_eq!(sidecar(Path::new("inputs/bob")), PathBuf::from("inputs/bob.answers"));
+
+        assert!(is_sidecar("alice.answers.txt") && is_sidecar("bob.answers"));
+        assert!(!is_sidecar("alice.txt") && !is_sidecar("bob") && !is_sidecar("answers.txt"));
+    }
+
+    #[test]
+    fn test_run_inputs() -> Result<()> {
+        let solution = registry::find(2023, 2).ok_or_else(|| eyre!("Day 2 is missing!"))?;
+        let directory = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        fs::create_dir_all(&directory)?;
+
+        fs::write(directory.join("alice.txt"), "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n")?;
+        fs::write(directory.join("alice.answers.txt"), "part 1: 1\npart 2: 48\n")?;
+        fs::write(directory.join("bob.txt"), "Game 1: 20 red\n")?;
+        fs::write(directory.join("bob.answers.txt"), "part 1: 1\npart 2: 20\n")?;
+        fs::write(directory.join("dave"), "Game 1: 1 red, 1 green, 1 blue\n")?;
+        fs::write(directory.join("dave.answers"), "part 1: 1\n")?;
+        fs::write(directory.join(".notes"), "not an input\n")?;
+
+        let paths = inputs(&directory)?;
+        assert_eq!(paths.iter().map(|path| name(path)).collect::<Vec<_>>(), vec!["alice.txt", "bob.txt", "dave"]);
+
+        let timeout = Duration::from_secs(10);
+        assert_eq!(run_inputs(solution, None, &directory, false, timeout)?, 0);
+        // Bob's part 1 is wrong, and Dave has no expected answer to part 2.
+        assert_eq!(run_inputs(solution, None, &directory, true, timeout)?, 2);
+        assert_eq!(run_inputs(solution, Some(1), &directory, true, timeout)?, 1);
+
+        fs::remove_file(directory.join("dave.answers"))?;
+        assert_eq!(run_inputs(solution, Some(2), &directory, true, timeout)?, 1);
+
+        fs::write(directory.join("carol.txt"), "Game one\n")?;
+        assert_eq!(run_inputs(solution, None, &directory, false, timeout)?, 2);
+        fs::remove_file(directory.join("carol.txt"))?;
+
+        // A part that fails on every input fails alone, and the other part is still solved.
+        static FAILING: Solution = Solution {
+            year: 2023,
+            day: 26,
+            package: "failing",
+            directory: "failing",
+            parts: &[|input| Ok(input.lines().count().to_string()), |_| Err(eyre!("no answer"))],
+            examples: &[],
+            board: None,
+        };
+        assert_eq!(run_inputs(&FAILING, None, &directory, false, timeout)?, 3);
+        assert_eq!(run_inputs(&FAILING, Some(1), &directory, false, timeout)?, 0);
+
+        fs::remove_dir_all(&directory)?;
+        assert!(run_inputs(solution, None, &directory, false, timeout).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_table() {
+        let report = |answers: &[&str]| {
+            let mut report = Report::new(2023, 4, "", utils::timing::Timings::default());
+            for (part, answer) in (1..).zip(answers) {
+                report.push(part, answer);
+            }
+
+            report
+        };
+
+        let outcomes = [
+            Outcome {
+                name: String::from("alice.txt"),
+                result: Ok(report(&["13", "30"])),
+                expected: Some(BTreeMap::from([(1, String::from("13")), (2, String::from("31"))])),
+            },
+            Outcome { name: String::from("bob.txt"), result: Err(String::from("malformed")), expected: None },
+            Outcome {
+                name: String::from("carol.txt"),
+                result: Ok({
+                    let mut report = report(&["7"]);
+                    report.push_failure(2, "no answer");
+                    report
+                }),
+                expected: None,
+            },
+        ];
+
+        assert_eq!(
+            table(&[1, 2], &outcomes),
+            "Input      Part 1    Time  Part 2    Time\n\
+             alice.txt    13 ✓  0.00ns    30 ✗  0.00ns\n\
+             bob.txt    failed       -  failed       -\n\
+             carol.txt       7  0.00ns  failed       -\n",
+        );
+        assert_eq!(outcomes.iter().map(|outcome| outcome.problems(&[1, 2])).collect::<Vec<_>>(), vec![1, 2, 1]);
+    }
+}
@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::Result;
+
+#[cfg(feature = "profile-alloc")]
+#[global_allocator]
+static ALLOCATOR: utils::alloc::CountingAllocator = utils::alloc::CountingAllocator;
+
+mod bench;
+mod examples;
+mod generate;
+mod inputs;
+#[cfg(unix)]
+mod profile;
+mod registry;
+mod run;
+mod scaffold;
+mod tui;
+mod vault;
+mod watch;
+
+/// Tools for running and testing the solutions of every day.
+#[derive(Debug, Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// List the solutions of every year.
+    List,
+    /// Run the solutions of a year, a day or a part, and check their answers against the known ones.
+    Run(run::RunArgs),
+    /// Benchmark the solutions of a year, a day or a part, and append the results to the performance history.
+    Bench(bench::BenchArgs),
+    /// Show how the benchmarks changed over time, and flag the parts that got slower.
+    PerfHistory(bench::PerfHistoryArgs),
+    /// Run a part over and over under a sampling profiler, and write a flamegraph of where the time goes.
+    #[cfg(unix)]
+    Profile(profile::ProfileArgs),
+    /// Explore the solutions in an interactive terminal UI.
+    Tui(tui::TuiArgs),
+    /// Rerun the tests and the input of a day whenever its sources or its input change.
+    Watch(watch::WatchArgs),
+    /// Create the crate of a new day, like `y2023/day_05`, and add it to the workspace.
+    New {
+        /// The year of the puzzle.
+        #[arg(long)]
+        year: u16,
+        /// The day of the puzzle.
+        #[arg(long)]
+        day: u8,
+        /// The root of the workspace.
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// Extract the examples of a day and their answers from its saved puzzle page, into its `examples` module.
+    Examples(examples::ExamplesArgs),
+    /// Generate a random, valid input for a day, for benchmarking and stress testing.
+    Generate {
+        #[command(flatten)]
+        args: generate::GenerateArgs,
+
+        /// Write the input to the given file instead of standard output.
+        #[arg(long, short, value_name = "PATH", global = true)]
+        output: Option<PathBuf>,
+    },
+    /// Encrypt, decrypt and rotate the inputs of every day, so they can be committed without publishing them.
+    Vault {
+        #[command(subcommand)]
+        command: vault::VaultCommand,
+    },
+}
+
+fn main() -> ExitCode {
+    utils::error::run(run)
+}
+
+fn run() -> Result<()> {
+    match Cli::parse().command {
+        Command::List => {
+            for solution in registry::solutions() {
+                let parts = solution.parts.len();
+                println!("{solution}: {parts} parts, package `{}` in {}", solution.package, solution.directory);
+            }
+        }
+        Command::Run(args) => run::run(&args)?,
+        Command::Bench(args) => bench::bench(&args)?,
+        Command::PerfHistory(args) => bench::perf_history(&args)?,
+        #[cfg(unix)]
+        Command::Profile(args) => profile::profile(&args)?,
+        Command::Tui(args) => tui::tui(&args)?,
+        Command::Watch(args) => watch::watch(&args)?,
+        Command::Examples(args) => examples::examples(&args)?,
+        Command::New { year, day, root } => {
+            let path = scaffold::scaffold(&root, year, day)?;
+            println!("Created {}.", path.display());
+            println!("Registered it in aoc/src/registry/y{year}/mod.rs.");
+            println!("Solve it in {} to run it with `aoc run`.", path.join("src/lib.rs").display());
+        }
+        Command::Generate { args, output } => {
+            let input = generate::generate(&args)?;
+
+            match output {
+                Some(path) => std::fs::write(path, input)?,
+                None => print!("{input}"),
+            }
+        }
+        Command::Vault { command } => vault::run(&command)?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use pprof::ProfilerGuardBuilder;
+
+use crate::registry::{self, Solution};
+
+/// Options for profiling a part.
+#[derive(Debug, Args)]
+pub struct ProfileArgs {
+    /// The year of the day, the latest year by default.
+    #[arg(long)]
+    pub year: Option<u16>,
+
+    /// The day to profile.
+    #[arg(long)]
+    pub day: u8,
+
+    /// The part to profile.
+    #[arg(long)]
+    pub part: u8,
+
+    /// How many seconds to run the part for, over and over.
+    #[arg(long, value_name = "SECONDS", default_value_t = 5)]
+    pub duration: u64,
+
+    /// How many samples to take per second.
+    #[arg(long, default_value_t = 999)]
+    pub frequency: i32,
+
+    /// The directory the flamegraph and the folded stacks are written to.
+    #[arg(long, short, value_name = "DIRECTORY", default_value = "target/profile")]
+    pub output: PathBuf,
+
+    /// The root of the workspace, where the input is read from.
+    #[arg(long, default_value = ".")]
+    pub root: PathBuf,
+}
+
+/// Run a part over and over under a sampling profiler, and write a flamegraph and the folded stacks of the samples.
+///
+/// # Arguments
+///
+/// * `args` - The part to profile and how.
+///
+/// # Returns
+///
+/// * `Result<()>` - Nothing.
+///
+/// # Errors
+///
+/// * `Report` - If there is no such part, it fails, or the profiler or its output fails.
+pub fn profile(args: &ProfileArgs) -> Result<()> {
+    let year = args.year.unwrap_or_else(registry::latest_year);
+    let solution =
+        registry::find(year, args.day).ok_or_else(|| eyre!("There is no solution to {year} day {}!", args.day))?;
+    let input = solution.input(&args.root)?;
+
+    println!("Profiling {solution} part {} for {}s…", args.part, args.duration);
+    let profile = sample(solution, args.part, &input, Duration::from_secs(args.duration), args.frequency)?;
+
+    fs::create_dir_all(&args.output).wrap_err_with(|| format!("Failed to create {}!", args.output.display()))?;
+    let name = format!("{year}-day-{}-part-{}", args.day, args.part);
+    let svg = args.output.join(format!("{name}.svg"));
+    let folded = args.output.join(format!("{name}.folded"));
+
+    fs::write(&folded, &profile.folded).wrap_err_with(|| format!("Failed to write {}!", folded.display()))?;
+    write_flamegraph(&profile.report, &svg)?;
+
+    println!(
+        "Ran {solution} part {} {} times and took {} samples, wrote {} and {}.",
+        args.part,
+        profile.runs,
+        profile.samples,
+        svg.display(),
+        folded.display(),
+    );
+
+    Ok(())
+}
+
+/// The samples taken while profiling a part.
+struct Profile {
+    report: pprof::Report,
+    /// The samples as folded stacks, a line like `main;solve;part_1 12` per stack.
+    folded: String,
+    runs: u32,
+    samples: isize,
+}
+
+/// Run a part over and over for the given time, or at least once, under the profiler.
+fn sample(solution: &Solution, part: u8, input: &str, duration: Duration, frequency: i32) -> Result<Profile> {
+    let guard = ProfilerGuardBuilder::default().frequency(frequency).build()?;
+
+    let start = Instant::now();
+    let mut runs = 0;
+    while runs == 0 || start.elapsed() < duration {
+        solution.run(part, input)?;
+        runs += 1;
+    }
+
+    let report = guard.report().build()?;
+    let folded = folded(&report);
+    let samples = report.data.values().sum();
+
+    Ok(Profile { report, folded, runs, samples })
+}
+
+/// Fold the stacks of a report, from the thread down to the sampled function, in the format of `inferno` and
+/// `flamegraph.pl`.
+fn folded(report: &pprof::Report) -> String {
+    let mut lines = report
+        .data
+        .iter()
+        .map(|(stack, count)| {
+            let frames = stack.frames.iter().rev().flat_map(|frame| frame.iter().rev());
+            let mut line = stack.thread_name_or_id();
+            for symbol in frames {
+                let _ = write!(line, ";{symbol}");
+            }
+
+            format!("{line} {count}")
+        })
+        .collect::<Vec<_>>();
+    lines.sort_unstable();
+
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+fn write_flamegraph(report: &pprof::Report, path: &Path) -> Result<()> {
+    File::create(path)
+        .map_err(color_eyre::Report::from)
+        .and_then(|file| Ok(report.flamegraph(file)?))
+        .wrap_err_with(|| format!("Failed to write {}!", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample() -> Result<()> {
+        let solution = registry::find(2023, 4).ok_or_else(|| eyre!("Day 4 is missing!"))?;
+        let input = solution.example(2).ok_or_else(|| eyre!("Day 4 has no example!"))?;
+
+        let profile = sample(solution, 1, input, Duration::ZERO, 999)?;
+        assert_eq!(profile.runs, 1);
+
+        let profile = sample(solution, 2, input, Duration::from_millis(300), 999)?;
+        assert!(profile.runs > 1);
+        assert!(profile.samples > 0);
+        let count = |line: &str| line.rsplit_once(' ').and_then(|(_, count)| count.parse::<isize>().ok());
+        assert_eq!(profile.folded.lines().map(count).sum::<Option<isize>>(), Some(profile.samples));
+
+        let path = std::env::temp_dir().join(format!("aoc-profile-{}.svg", std::process::id()));
+        write_flamegraph(&profile.report, &path)?;
+        assert!(fs::read_to_string(&path)?.starts_with("<?xml"));
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,244 @@
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use utils::example::Example;
+use utils::grid::Grid;
+use utils::render::Style;
+use utils::report::Report;
+use utils::timing;
+use utils::vault::EXTENSION;
+
+mod y2023;
+
+/// A part of a solution, which parses the input and returns the answer.
+///
+/// Parts record their parsing and solving in the `parse` and `part N` spans of [`utils::timing`].
+pub type Part = fn(&str) -> Result<String>;
+
+/// The solution to a day of a year.
+///
+/// # Fields
+///
+/// * `year` - The year of the puzzle.
+/// * `day` - The day of the puzzle.
+/// * `package` - The package of the solution, e.g. `day_4` or `y2023_day_05`.
+/// * `directory` - The directory of the package, relative to the root of the workspace.
+/// * `parts` - The parts, in order.
+/// * `examples` - The example of every part and its answer, from the puzzle; the last one also stands for any later
+///   parts.
+/// * `board` - How to draw the input as a board, for days on a grid.
+#[derive(Debug)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub package: &'static str,
+    pub directory: &'static str,
+    pub parts: &'static [Part],
+    pub examples: &'static [Example],
+    pub board: Option<fn(&str) -> Result<Board>>,
+}
+
+/// An input drawn as a board of highlighted cells.
+///
+/// # Fields
+///
+/// * `cells` - The character of every cell.
+/// * `styles` - The style of every cell, or `None` for cells that are not highlighted.
+#[derive(Debug, Clone)]
+pub struct Board {
+    pub cells: Grid<char>,
+    pub styles: Grid<Option<Style>>,
+}
+
+impl Solution {
+    /// The directory of the solution, which holds its input and its answers.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the workspace.
+    ///
+    /// # Returns
+    ///
+    /// * `PathBuf` - The directory.
+    #[must_use]
+    pub fn directory(&self, root: &Path) -> PathBuf {
+        root.join(self.directory)
+    }
+
+    /// Read the input of the solution, which is `input.txt` or else the encrypted `input.txt.enc`.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the workspace.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Cow<str>>` - The input.
+    ///
+    /// # Errors
+    ///
+    /// * `Report` - If there is no input, or it cannot be read or decrypted.
+    pub fn input(&self, root: &Path) -> Result<Cow<'static, str>> {
+        let plain = self.directory(root).join("input.txt");
+        let path = if plain.exists() { plain } else { self.directory(root).join(format!("input.txt.{EXTENSION}")) };
+
+        utils::error::read_input(Some(&path), b"").wrap_err_with(|| format!("Failed to read the input of {self}!"))
+    }
+
+    /// Run a part of the solution, recording its spans.
+    ///
+    /// # Arguments
+    ///
+    /// * `part` - The part to run, starting at 1.
+    /// * `input` - The input.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Report>` - A report with the answer to the part and its spans.
+    ///
+    /// # Errors
+    ///
+    /// * `Report` - If there is no such part, or it fails.
+    pub fn run(&self, part: u8, input: &str) -> Result<Report> {
+        let solve = usize::from(part)
+            .checked_sub(1)
+            .and_then(|index| self.parts.get(index))
+            .ok_or_else(|| eyre!("{self} has no part {part}!"))?;
+
+        // Drop any spans left over from an earlier run that failed.
+        let _ = timing::take();
+        let answer = solve(input).wrap_err_with(|| format!("Failed to solve part {part} of {self}!"))?;
+
+        let mut report = Report::new(self.year, self.day, input, timing::take());
+        report.push(part, answer);
+
+        Ok(report)
+    }
+
+    /// The example input of a part.
+    ///
+    /// # Arguments
+    ///
+    /// * `part` - The part, starting at 1.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The example, if the solution has any.
+    #[must_use]
+    pub fn example(&self, part: u8) -> Option<&'static str> {
+        let index = usize::from(part).saturating_sub(1).min(self.examples.len().checked_sub(1)?);
+
+        self.examples.get(index).map(|example| example.input)
+    }
+
+    /// The file holding the known answers of the solution, one per line like `part 1: 26443`.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the workspace.
+    ///
+    /// # Returns
+    ///
+    /// * `PathBuf` - The path of `answers.txt`.
+    #[must_use]
+    pub fn answers_path(&self, root: &Path) -> PathBuf {
+        self.directory(root).join("answers.txt")
+    }
+}
+
+impl Display for Solution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/// The solutions of every year, ordered by year. `aoc new` adds the years it scaffolds, one per line.
+static YEARS: &[&[Solution]] = &[
+    &y2023::SOLUTIONS,
+];
+
+/// Iterate over every solution, ordered by year and day.
+///
+/// # Returns
+///
+/// * `impl Iterator<Item = &Solution>` - The solutions.
+pub fn solutions() -> impl Iterator<Item = &'static Solution> {
+    YEARS.iter().flat_map(|solutions| solutions.iter())
+}
+
+/// Find the solution to a day of a year.
+///
+/// # Arguments
+///
+/// * `year` - The year of the puzzle.
+/// * `day` - The day of the puzzle.
+///
+/// # Returns
+///
+/// * `Option<&Solution>` - The solution, if there is one.
+#[must_use]
+pub fn find(year: u16, day: u8) -> Option<&'static Solution> {
+    solutions().find(|solution| solution.year == year && solution.day == day)
+}
+
+/// The latest year with any solutions, which commands use when no year is given.
+///
+/// # Returns
+///
+/// * `u16` - The year.
+#[must_use]
+pub fn latest_year() -> u16 {
+    solutions().map(|solution| solution.year).max().unwrap_or(2023)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let years_and_days = solutions().map(|solution| (solution.year, solution.day)).collect::<Vec<_>>();
+        let mut sorted = years_and_days.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        assert_eq!(years_and_days, sorted);
+        assert_eq!(find(2023, 4).map(|solution| solution.package), Some("day_4"));
+        assert!(find(2022, 4).is_none());
+        // A new day has no examples until they are extracted from its page, but no day has more examples than parts.
+        assert!(solutions().all(|solution| solution.examples.len() <= solution.parts.len()));
+        assert_eq!(latest_year(), 2023);
+    }
+
+    #[test]
+    fn test_run() -> Result<()> {
+        let solution = find(2023, 1).ok_or_else(|| eyre!("Day 1 is missing!"))?;
+        let report = solution.run(2, "two1nine\neightwothree")?;
+
+        assert_eq!((report.year, report.day), (2023, 1));
+        assert_eq!(report.parts.len(), 1);
+        assert_eq!((report.parts[0].part, report.parts[0].answer.as_str()), (2, "112"));
+        assert!(report.timings.get("part 2").is_some());
+
+        assert!(solution.run(3, "").is_err());
+        assert_eq!(solution.example(3), solution.example(2));
+        assert_ne!(solution.example(1), solution.example(2));
+        assert!(find(2023, 4).is_some_and(|solution| solution.run(1, "Card 1: x | 2").is_err()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_examples() -> Result<()> {
+        for solution in solutions() {
+            for (part, example) in (1..).zip(solution.examples) {
+                let report = solution.run(part, example.input)?;
+                assert_eq!(report.parts[0].answer, example.answer, "{solution} part {part}");
+            }
+        }
+
+        Ok(())
+    }
+}
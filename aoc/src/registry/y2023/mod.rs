@@ -0,0 +1,104 @@
+//! The solutions of 2023, which predate the year-aware layout and so live at the root of the workspace.
+
+use color_eyre::eyre::Result;
+use day_4::OverflowPolicy;
+use utils::error::Error;
+use utils::timing;
+
+use super::{Board, Solution};
+
+pub static SOLUTIONS: [Solution; 4] = [
+    Solution {
+        year: 2023,
+        day: 1,
+        package: "day_1",
+        directory: "day_1",
+        parts: &[day_1_part_1, day_1_part_2],
+        examples: &day_1::examples::EXAMPLES,
+        board: None,
+    },
+    Solution {
+        year: 2023,
+        day: 2,
+        package: "day_2",
+        directory: "day_2",
+        parts: &[day_2_part_1, day_2_part_2],
+        examples: &day_2::examples::EXAMPLES,
+        board: None,
+    },
+    Solution {
+        year: 2023,
+        day: 3,
+        package: "day_3",
+        directory: "day_3",
+        parts: &[day_3_part_1, day_3_part_2],
+        examples: &day_3::examples::EXAMPLES,
+        board: Some(day_3_board),
+    },
+    Solution {
+        year: 2023,
+        day: 4,
+        package: "day_4",
+        directory: "day_4",
+        parts: &[day_4_part_1, day_4_part_2],
+        examples: &day_4::examples::EXAMPLES,
+        board: None,
+    },
+];
+
+fn day_1_part_1(input: &str) -> Result<String> {
+    Ok(timing::span("part 1", || day_1::part_1::calibrate_lines(input)).to_string())
+}
+
+fn day_1_part_2(input: &str) -> Result<String> {
+    Ok(timing::span("part 2", || day_1::part_2::calibrate_lines(input)).to_string())
+}
+
+fn day_2_part_1(input: &str) -> Result<String> {
+    let games = timing::span("parse", || day_2::part_1::parse_games_cached(input))?;
+
+    Ok(timing::span("part 1", || day_2::part_1::are_games_possible(&games))?.to_string())
+}
+
+fn day_2_part_2(input: &str) -> Result<String> {
+    let games = timing::span("parse", || day_2::part_1::parse_games_cached(input))?;
+
+    let power = timing::span("part 2", || {
+        day_2::part_2::sum_of_powers(&games.iter().map(day_2::part_2::is_game_possible).collect::<Vec<_>>())
+    })?;
+
+    Ok(power.to_string())
+}
+
+fn day_3_part_1(input: &str) -> Result<String> {
+    let schematic = timing::span("parse", || day_3::Schematic::parse_cached(input)).map_err(Error::from)?;
+
+    Ok(timing::span("part 1", || schematic.sum_of_parts())?.to_string())
+}
+
+fn day_3_part_2(input: &str) -> Result<String> {
+    let schematic = timing::span("parse", || day_3::Schematic::parse_cached(input)).map_err(Error::from)?;
+
+    Ok(timing::span("part 2", || schematic.sum_of_gear_ratios())?.to_string())
+}
+
+fn day_3_board(input: &str) -> Result<Board> {
+    let schematic = day_3::Schematic::parse_cached(input).map_err(Error::from)?;
+
+    Ok(Board {
+        cells: schematic.grid().clone(),
+        styles: day_3::render::styles(&schematic),
+    })
+}
+
+fn day_4_part_1(input: &str) -> Result<String> {
+    let cards = timing::span("parse", || day_4::parse_cards_cached(input))?;
+
+    Ok(timing::span("part 1", || day_4::sum_points(&cards))?.to_string())
+}
+
+fn day_4_part_2(input: &str) -> Result<String> {
+    let cards = timing::span("parse", || day_4::parse_cards_cached(input))?;
+
+    Ok(timing::span("part 2", || day_4::count_copies(&cards, OverflowPolicy::Error))?.to_string())
+}
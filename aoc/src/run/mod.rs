@@ -0,0 +1,488 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use color_eyre::eyre::{ensure, eyre, Result, WrapErr};
+use utils::report::{Format, PartReport, Report, Status};
+use utils::timing::Timings;
+
+use crate::inputs;
+use crate::registry::{self, Solution};
+
+/// Options for running solutions.
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// The year to run, the latest year by default.
+    #[arg(long)]
+    pub year: Option<u16>,
+
+    /// The day to run, every day of the year by default.
+    #[arg(long)]
+    pub day: Option<u8>,
+
+    /// The part to run, every part by default.
+    #[arg(long)]
+    pub part: Option<u8>,
+
+    /// Read the input from the given file instead of the day's own input, which needs `--day`.
+    #[arg(long, value_name = "PATH", requires = "day")]
+    pub input: Option<PathBuf>,
+
+    /// Run on every input in the given directory instead, and print their answers side by side, which needs `--day`.
+    #[arg(long, value_name = "DIRECTORY", requires = "day", conflicts_with_all = ["input", "report", "save_answers"])]
+    pub inputs: Option<PathBuf>,
+
+    /// Check the answers on every input of `--inputs` against its expected answers, e.g. `alice.answers.txt`
+    /// for `alice.txt`. A part without an expected answer fails the check.
+    #[arg(long, requires = "inputs")]
+    pub check: bool,
+
+    /// Print a report in the given format (`json`, `csv` or `markdown`), which needs `--day`.
+    #[arg(long, requires = "day")]
+    pub report: Option<Format>,
+
+    /// Save the answers to the `answers.txt` of every day, instead of checking them against it.
+    #[arg(long, conflicts_with = "input")]
+    pub save_answers: bool,
+
+    /// Reuse the parsed inputs from earlier runs on the same inputs, see `utils::cache`.
+    #[arg(long)]
+    pub cache: bool,
+
+    /// Give up on a part that runs for longer than this many seconds, and go on with the rest.
+    #[arg(long, value_name = "SECONDS", default_value = "60", value_parser = parse_seconds)]
+    pub timeout: Duration,
+
+    /// The root of the workspace.
+    #[arg(long, default_value = ".")]
+    pub root: PathBuf,
+}
+
+/// Run the selected solutions, and check their answers against the known ones.
+///
+/// # Arguments
+///
+/// * `args` - The solutions to run and how.
+///
+/// # Returns
+///
+/// * `Result<()>` - Nothing.
+///
+/// # Errors
+///
+/// * `Report` - If no solution is selected, a day has no input, a part fails, times out or panics, or an answer does
+///   not match the known or expected one. Every selected day is still run, and these are only reported at the end.
+pub fn run(args: &RunArgs) -> Result<()> {
+    if args.cache {
+        utils::cache::enable();
+    }
+
+    let year = args.year.unwrap_or_else(registry::latest_year);
+    let solutions = match args.day {
+        Some(day) => vec![registry::find(year, day).ok_or_else(|| eyre!("There is no solution to {year} day {day}!"))?],
+        None => registry::solutions().filter(|solution| solution.year == year).collect(),
+    };
+    ensure!(!solutions.is_empty(), "There are no solutions to {year}!");
+
+    if let Some(directory) = &args.inputs {
+        let problems = inputs::run_inputs(solutions[0], args.part, directory, args.check, args.timeout)?;
+        ensure!(problems == 0, "{problems} answers are wrong, unexpected, failed, timed out or panicked!");
+
+        return Ok(());
+    }
+
+    let mut wrong = 0;
+    let mut unsolved = Vec::new();
+    for solution in solutions {
+        let input = match &args.input {
+            Some(path) => utils::error::read_input(Some(path), b"").map_err(color_eyre::Report::from),
+            None => solution.input(&args.root),
+        };
+
+        // A day without an input fails every part, like a part that fails on its own, so the other days still run.
+        let report = match input {
+            Ok(input) => run_parts(solution, args.part, &input, Some(args.timeout))?,
+            Err(error) => {
+                let mut report = Report::new(solution.year, solution.day, "", Timings::default());
+                for part in selected_parts(solution, args.part)? {
+                    report.push_failure(part, describe(&error));
+                }
+
+                report
+            }
+        };
+        unsolved.extend(report.parts.iter().map(|part| part.status.clone()).filter(|status| *status != Status::Solved));
+
+        let answers_path = solution.answers_path(&args.root);
+        let known = if args.input.is_some() { BTreeMap::new() } else { read_answers(&answers_path)? };
+
+        if let Some(format) = args.report {
+            print!("{}", report.render(format));
+        } else {
+            for part in &report.parts {
+                match &part.status {
+                    Status::Solved => {}
+                    Status::TimedOut | Status::Panicked => {
+                        println!("{solution} part {}: {} after {:.2?}", part.part, part.status, part.solve);
+                        continue;
+                    }
+                    Status::Failed(_) => {
+                        println!("{solution} part {}: {}", part.part, part.status);
+                        continue;
+                    }
+                }
+
+                let check = match known.get(&part.part) {
+                    Some(answer) if *answer == part.answer => " (correct)".to_string(),
+                    Some(answer) => format!(" (wrong, expected {answer})"),
+                    None => String::new(),
+                };
+                println!(
+                    "{solution} part {}: {}{check}   [parse {:.2?}, solve {:.2?}]",
+                    part.part, part.answer, part.parse, part.solve,
+                );
+            }
+        }
+
+        if args.save_answers {
+            let mut answers = known;
+            let solved = report.parts.iter().filter(|part| part.is_solved());
+            answers.extend(solved.map(|part| (part.part, part.answer.clone())));
+            write_answers(&answers_path, &answers)?;
+        } else {
+            let is_wrong = |part: &&PartReport| {
+                part.is_solved() && known.get(&part.part).is_some_and(|answer| *answer != part.answer)
+            };
+            wrong += report.parts.iter().filter(is_wrong).count();
+        }
+    }
+
+    let count = |is: fn(&Status) -> bool| unsolved.iter().filter(|status| is(status)).count();
+    let problems = [
+        (wrong, String::from("do not match the known answers")),
+        (count(|status| *status == Status::TimedOut), format!("timed out after {:.2?}", args.timeout)),
+        (count(|status| *status == Status::Panicked), String::from("panicked")),
+        (count(|status| matches!(status, Status::Failed(_))), String::from("failed")),
+    ];
+    let problems = problems
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, problem)| format!("{count} parts {problem}"))
+        .collect::<Vec<_>>();
+    ensure!(problems.is_empty(), "{}!", problems.join(", "));
+
+    Ok(())
+}
+
+/// The parts of a solution to run.
+///
+/// # Arguments
+///
+/// * `solution` - The solution.
+/// * `part` - The part to run, or `None` for every part.
+///
+/// # Returns
+///
+/// * `Result<Vec<u8>>` - The parts, starting at 1.
+///
+/// # Errors
+///
+/// * `Report` - If the solution has no such part.
+pub(crate) fn selected_parts(solution: &Solution, part: Option<u8>) -> Result<Vec<u8>> {
+    let count = u8::try_from(solution.parts.len())?;
+
+    match part {
+        Some(part) => {
+            ensure!((1..=count).contains(&part), "{solution} has no part {part}!");
+
+            Ok(vec![part])
+        }
+        None => Ok((1..=count).collect()),
+    }
+}
+
+/// Describe an error in a single line, with every cause after it.
+pub(crate) fn describe(error: &color_eyre::Report) -> String {
+    error.chain().map(|cause| cause.to_string().trim_end_matches('!').to_owned()).collect::<Vec<_>>().join(": ")
+}
+
+/// Run the given part of a solution, or every part, into a single report.
+///
+/// # Arguments
+///
+/// * `solution` - The solution to run.
+/// * `part` - The part to run, or `None` for every part.
+/// * `input` - The input.
+/// * `timeout` - How long each part may run, or `None` to wait for as long as it takes.
+///
+/// # Returns
+///
+/// * `Result<Report>` - A report with the status and answer of every part, and the spans of every part one after the
+///   other. A part that fails is marked as failed, and, given a timeout, a part that runs out of time or panics is
+///   marked as timed out or as panicked, so the other parts are still run.
+///
+/// # Errors
+///
+/// * `Report` - If there is no such part, or a thread cannot be spawned.
+pub fn run_parts(
+    solution: &'static Solution,
+    part: Option<u8>,
+    input: &str,
+    timeout: Option<Duration>,
+) -> Result<Report> {
+    let mut reports = selected_parts(solution, part)?.into_iter().map(|part| match timeout {
+        Some(timeout) => run_with_timeout(solution, part, input, timeout),
+        None => Ok(run_part(solution, part, input)),
+    });
+    let mut report = reports.next().ok_or_else(|| eyre!("{solution} has no parts!"))??;
+    for next in reports {
+        let next = next?;
+        report.parts.extend(next.parts);
+        report.timings.0.extend(next.timings.0);
+    }
+
+    Ok(report)
+}
+
+/// Run a part of a solution, and record it as failed if it returns an error, with the error that caused it.
+fn run_part(solution: &Solution, part: u8, input: &str) -> Report {
+    solution.run(part, input).unwrap_or_else(|error| {
+        let mut report = Report::new(solution.year, solution.day, input, Timings::default());
+        report.push_failure(part, error.root_cause());
+
+        report
+    })
+}
+
+/// Run a part of a solution on a thread of its own, and give up on it once it runs out of time.
+///
+/// A part cannot be interrupted, so one that runs out of time is abandoned: its thread keeps running in the
+/// background until the runner exits, and any allocations it makes are counted towards the parts after it.
+///
+/// # Arguments
+///
+/// * `solution` - The solution to run.
+/// * `part` - The part to run.
+/// * `input` - The input.
+/// * `timeout` - How long the part may run.
+///
+/// # Returns
+///
+/// * `Result<Report>` - A report with the answer to the part, or with the part marked as failed, timed out or
+///   panicked. The panic message itself is printed to stderr by the panic hook.
+///
+/// # Errors
+///
+/// * `Report` - If there is no such part, or its thread cannot be spawned.
+pub(crate) fn run_with_timeout(
+    solution: &'static Solution,
+    part: u8,
+    input: &str,
+    timeout: Duration,
+) -> Result<Report> {
+    selected_parts(solution, Some(part))?;
+
+    let (sender, receiver) = mpsc::channel();
+    let owned = input.to_string();
+
+    let start = Instant::now();
+    thread::Builder::new().name(format!("{solution} part {part}")).spawn(move || {
+        let _ = sender.send(run_part(solution, part, &owned));
+    })?;
+
+    let received = receiver.recv_timeout(timeout);
+    let mut report = Report::new(solution.year, solution.day, input, Timings::default());
+    match received {
+        Ok(report) => return Ok(report),
+        Err(RecvTimeoutError::Timeout) => report.push_timeout(part, timeout),
+        // The thread dropped its sender without sending a report, so it panicked.
+        Err(RecvTimeoutError::Disconnected) => report.push_panic(part, start.elapsed()),
+    }
+
+    Ok(report)
+}
+
+/// Parse a number of seconds, like `60` or `0.5`.
+pub(crate) fn parse_seconds(seconds: &str) -> Result<Duration, String> {
+    seconds
+        .parse()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .filter(|seconds| !seconds.is_zero())
+        .ok_or_else(|| format!("`{seconds}` is not a positive number of seconds"))
+}
+
+/// Read the known answers of a solution, like `part 1: 26443` on every line.
+///
+/// # Arguments
+///
+/// * `path` - The answers file.
+///
+/// # Returns
+///
+/// * `Result<BTreeMap<u8, String>>` - The answer to every part, empty if the file does not exist.
+///
+/// # Errors
+///
+/// * `Report` - If the file cannot be read or is malformed.
+pub fn read_answers(path: &Path) -> Result<BTreeMap<u8, String>> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let answers = fs::read_to_string(path).wrap_err_with(|| format!("Failed to read {}!", path.display()))?;
+
+    answers
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            line.strip_prefix("part ")
+                .and_then(|line| line.split_once(':'))
+                .and_then(|(part, answer)| Some((part.trim().parse().ok()?, answer.trim().to_string())))
+                .ok_or_else(|| eyre!("{}:{}: expected an answer like `part 1: 42`!", path.display(), index + 1))
+        })
+        .collect()
+}
+
+/// Write the known answers of a solution, in the format read by [`read_answers`].
+///
+/// # Arguments
+///
+/// * `path` - The answers file.
+/// * `answers` - The answer to every part.
+///
+/// # Returns
+///
+/// * `Result<()>` - Nothing.
+///
+/// # Errors
+///
+/// * `Report` - If the file cannot be written.
+pub fn write_answers(path: &Path, answers: &BTreeMap<u8, String>) -> Result<()> {
+    let mut contents = String::new();
+    for (part, answer) in answers {
+        let _ = writeln!(contents, "part {part}: {answer}");
+    }
+
+    fs::write(path, contents).wrap_err_with(|| format!("Failed to write {}!", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_parts() -> Result<()> {
+        let solution = registry::find(2023, 2).ok_or_else(|| eyre!("Day 2 is missing!"))?;
+        let report = run_parts(solution, None, "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", None)?;
+
+        let answers = report.parts.iter().map(|part| (part.part, part.answer.as_str())).collect::<Vec<_>>();
+        assert_eq!(answers, vec![(1, "1"), (2, "48")]);
+
+        assert_eq!(run_parts(solution, Some(2), "Game 1: 3 blue", None)?.parts.len(), 1);
+        assert!(run_parts(solution, Some(3), "Game 1: 3 blue", None).is_err());
+        assert!(run_parts(solution, Some(0), "Game 1: 3 blue", None).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_parts_failure() -> Result<()> {
+        fn fails(_: &str) -> Result<String> {
+            Err(utils::error::Error::Unsolvable(String::from("there is no answer")))?
+        }
+
+        static SOLUTION: Solution = Solution {
+            year: 2023,
+            day: 26,
+            package: "fails",
+            directory: "fails",
+            parts: &[fails, |input| Ok(input.len().to_string())],
+            examples: &[],
+            board: None,
+        };
+
+        // The part after the failing one is still solved, with or without a time budget.
+        for timeout in [None, Some(Duration::from_secs(10))] {
+            let report = run_parts(&SOLUTION, None, "abc", timeout)?;
+            let statuses =
+                report.parts.iter().map(|part| (part.status.clone(), part.answer.as_str())).collect::<Vec<_>>();
+            let failed = Status::Failed(String::from("the input has no answer: there is no answer"));
+            assert_eq!(statuses, [(failed, ""), (Status::Solved, "3")]);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_timeout() -> Result<()> {
+        fn quick(input: &str) -> Result<String> {
+            Ok(input.len().to_string())
+        }
+
+        fn slow(_: &str) -> Result<String> {
+            thread::sleep(Duration::from_secs(5));
+
+            Ok(String::from("too late"))
+        }
+
+        fn panics(_: &str) -> Result<String> {
+            panic!("part 3 is broken");
+        }
+
+        static SOLUTION: Solution = Solution {
+            year: 2023,
+            day: 26,
+            package: "slow",
+            directory: "slow",
+            parts: &[quick, slow, panics],
+            examples: &[],
+            board: None,
+        };
+
+        // A panic is reported as soon as the part unwinds, which may take a while with backtraces on.
+        let report = run_parts(&SOLUTION, Some(3), "abc", Some(Duration::from_secs(10)))?;
+        assert_eq!(report.parts[0].status, Status::Panicked);
+
+        let report = run_parts(&SOLUTION, None, "abc", Some(Duration::from_millis(50)))?;
+        assert_eq!(report.parts.iter().map(|part| part.part).collect::<Vec<_>>(), vec![1, 2, 3]);
+        let statuses = report.parts.iter().map(|part| part.status.clone()).collect::<Vec<_>>();
+        assert_eq!(statuses[..2], [Status::Solved, Status::TimedOut]);
+
+        let report = run_parts(&SOLUTION, Some(2), "abc", Some(Duration::from_millis(50)))?;
+        assert_eq!(report.parts[0].status, Status::TimedOut);
+        assert_eq!(report.input_hash, Report::new(2023, 26, "abc", Timings::default()).input_hash);
+
+        let report = run_parts(&SOLUTION, Some(1), "abc", Some(Duration::from_millis(50)))?;
+        assert_eq!((report.parts[0].answer.as_str(), report.parts[0].is_solved()), ("3", true));
+
+        assert_eq!(parse_seconds("0.5"), Ok(Duration::from_millis(500)));
+        assert!(parse_seconds("0").is_err() && parse_seconds("-1").is_err() && parse_seconds("soon").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_answers() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.txt", std::process::id()));
+        let answers = BTreeMap::from([(1, "26443".to_string()), (2, "6284877".to_string())]);
+
+        write_answers(&path, &answers)?;
+        assert_eq!(fs::read_to_string(&path)?, "part 1: 26443\npart 2: 6284877\n");
+        assert_eq!(read_answers(&path)?, answers);
+
+        fs::write(&path, "part one: 1\n")?;
+        assert!(read_answers(&path).is_err());
+
+        fs::remove_file(&path)?;
+        assert!(read_answers(&path)?.is_empty());
+
+        Ok(())
+    }
+}
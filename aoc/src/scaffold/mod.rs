@@ -0,0 +1,423 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{ensure, eyre, Result, WrapErr};
+
+use crate::registry;
+
+/// The directory of a new day, relative to the root of the workspace, e.g. `y2023/day_05`.
+///
+/// # Arguments
+///
+/// * `year` - The year of the puzzle.
+/// * `day` - The day of the puzzle.
+///
+/// # Returns
+///
+/// * `String` - The directory.
+#[must_use]
+pub fn directory(year: u16, day: u8) -> String {
+    format!("y{year}/day_{day:02}")
+}
+
+/// The package of a new day, e.g. `y2023_day_05`.
+///
+/// # Arguments
+///
+/// * `year` - The year of the puzzle.
+/// * `day` - The day of the puzzle.
+///
+/// # Returns
+///
+/// * `String` - The name of the package.
+#[must_use]
+pub fn package(year: u16, day: u8) -> String {
+    directory(year, day).replace('/', "_")
+}
+
+/// Create the crate of a new day, add it to the workspace and register it with the runner.
+///
+/// The crate becomes a dependency of `aoc`, and its parts are added to `aoc/src/registry/y<year>/mod.rs`, which is
+/// created with its `mod` declaration and its entry in the registry if the year is new.
+///
+/// # Arguments
+///
+/// * `root` - The root of the workspace.
+/// * `year` - The year of the puzzle.
+/// * `day` - The day of the puzzle.
+///
+/// # Returns
+///
+/// * `Result<PathBuf>` - The directory of the new crate.
+///
+/// # Errors
+///
+/// * `Report` - If the day is out of range, already exists, or a file cannot be read or written.
+pub fn scaffold(root: &Path, year: u16, day: u8) -> Result<PathBuf> {
+    ensure!((2015..=9999).contains(&year), "Advent of Code started in 2015, not {year}!");
+    ensure!((1..=25).contains(&day), "There is no day {day}, days go from 1 to 25!");
+    if let Some(solution) = registry::find(year, day) {
+        return Err(eyre!("{solution} already exists in {}!", solution.directory));
+    }
+
+    let directory = directory(year, day);
+    let path = root.join(&directory);
+    ensure!(!path.exists(), "{} already exists!", path.display());
+
+    let read = |path: &Path| fs::read_to_string(path).wrap_err_with(|| format!("Failed to read {}!", path.display()));
+    let package = package(year, day);
+
+    // Prepare every change before writing any, so a failure leaves the workspace as it was.
+    let manifest = root.join("Cargo.toml");
+    let workspace = add_member(&read(&manifest)?, &directory)?;
+
+    let runner_manifest = root.join("aoc/Cargo.toml");
+    let runner = add_dependency(&read(&runner_manifest)?, &package, &directory)?;
+
+    let registry_path = root.join("aoc/src/registry/mod.rs");
+    let registry = add_year(&read(&registry_path)?, year)?;
+
+    let year_path = root.join(format!("aoc/src/registry/y{year}/mod.rs"));
+    let year_module = if year_path.exists() { read(&year_path)? } else { YEAR_RS.replace("{year}", &year.to_string()) };
+    let year_module = add_solution(&year_module, year, day, &package, &directory)?;
+
+    fs::create_dir_all(path.join("src/examples"))?;
+    fs::write(path.join("Cargo.toml"), CARGO_TOML.replace("{package}", &package))?;
+    fs::write(path.join("build.rs"), BUILD_RS)?;
+    fs::write(path.join("src/lib.rs"), LIB_RS)?;
+    fs::write(
+        path.join("src/examples/mod.rs"),
+        EXAMPLES_RS.replace("{year}", &year.to_string()).replace("{day}", &day.to_string()),
+    )?;
+    fs::write(
+        path.join("src/main.rs"),
+        MAIN_RS
+            .replace("{package}", &package)
+            .replace("{year}", &year.to_string())
+            .replace("{day}", &day.to_string()),
+    )?;
+    fs::write(&manifest, workspace)?;
+
+    fs::write(&runner_manifest, runner)?;
+    fs::write(&registry_path, registry)?;
+    fs::create_dir_all(year_path.parent().unwrap_or(root))?;
+    fs::write(&year_path, year_module)?;
+
+    Ok(path)
+}
+
+/// Add a member to the `members` of a workspace manifest.
+fn add_member(manifest: &str, member: &str) -> Result<String> {
+    let start = manifest.find("members = [").ok_or_else(|| eyre!("The workspace has no `members`!"))?;
+    let end = manifest[start..]
+        .find(']')
+        .map(|end| start + end)
+        .ok_or_else(|| eyre!("The `members` of the workspace are not closed!"))?;
+
+    Ok(format!("{}    \"{member}\",\n{}", &manifest[..end], &manifest[end..]))
+}
+
+/// Add a path dependency to the end of the `[dependencies]` of a manifest.
+fn add_dependency(manifest: &str, package: &str, directory: &str) -> Result<String> {
+    let start = manifest.find("[dependencies]\n").ok_or_else(|| eyre!("The runner has no `[dependencies]`!"))?;
+    // The table ends at the next one, before the blank line separating them.
+    let end = manifest[start..].find("\n[").map_or(manifest.len(), |end| start + end);
+    let end = manifest[..end].trim_end_matches('\n').len() + 1;
+
+    Ok(format!("{}{package} = {{ path = \"../{directory}\" }}\n{}", &manifest[..end], &manifest[end..]))
+}
+
+/// Declare the module of a year in the registry and add its solutions to `YEARS`, unless it already has them.
+fn add_year(registry: &str, year: u16) -> Result<String> {
+    let module = format!("mod y{year};");
+    if registry.lines().any(|line| line == module) {
+        return Ok(registry.to_string());
+    }
+
+    let registry = insert_sorted(registry, "mod y", ";", &module)?;
+    let start = registry.find("static YEARS").ok_or_else(|| eyre!("The registry has no `YEARS`!"))?;
+
+    let (before, after) = registry.split_at(start);
+    Ok(format!("{before}{}", insert_sorted(after, "    &y", "::SOLUTIONS,", &format!("    &y{year}::SOLUTIONS,"))?))
+}
+
+/// Insert a line among the first run of consecutive lines that start with `prefix` and end with `suffix`, which are
+/// kept sorted.
+fn insert_sorted(text: &str, prefix: &str, suffix: &str, line: &str) -> Result<String> {
+    let lines = text.lines().collect::<Vec<_>>();
+    let is_match = |line: &&str| line.starts_with(prefix) && line.ends_with(suffix);
+
+    let start = lines.iter().position(is_match).ok_or_else(|| eyre!("There is no line like `{prefix}..{suffix}`!"))?;
+    let end = lines[start..].iter().position(|line| !is_match(line)).map_or(lines.len(), |end| start + end);
+
+    let mut run = lines[start..end].to_vec();
+    run.push(line);
+    run.sort_unstable();
+
+    let lines = lines[..start].iter().chain(&run).chain(&lines[end..]).copied().collect::<Vec<_>>();
+    Ok(format!("{}\n", lines.join("\n")))
+}
+
+/// Add a day to the `SOLUTIONS` of a year, ordered by day, with functions that run its parts.
+fn add_solution(module: &str, year: u16, day: u8, package: &str, directory: &str) -> Result<String> {
+    // The functions of the day would be defined twice, and the runner would not compile any more.
+    let parts = format!("fn day_{day}_part_");
+    ensure!(!module.contains(&parts), "{year} day {day} is already registered, its parts are `{parts}*`!");
+
+    let start = module.find("pub static SOLUTIONS").ok_or_else(|| eyre!("The year has no `SOLUTIONS`!"))?;
+    let end = module[start..].find("\n];").ok_or_else(|| eyre!("`SOLUTIONS` is not closed!"))? + start + 1;
+
+    // Insert the day before the first later one, or else at the end.
+    let later = module[start..end].match_indices("    Solution {").map(|(index, _)| start + index).find(|&index| {
+        let entry = &module[index..end];
+        let day_of = entry.find("day: ").and_then(|at| entry[at + 5..].split(',').next()?.parse::<u8>().ok());
+        day_of.is_some_and(|other| other > day)
+    });
+    let at = later.unwrap_or(end);
+
+    let entry = SOLUTION_RS
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+        .replace("{package}", package)
+        .replace("{directory}", directory);
+    let mut module = format!("{}{entry}{}", &module[..at], &module[at..]);
+
+    // `SOLUTIONS` is a fixed-size array, like the one of 2023, so it has one more.
+    if let Some(size) = module.find("[Solution; ").map(|at| at + "[Solution; ".len()) {
+        let digits = module[size..].find(']').map(|end| size + end).ok_or_else(|| eyre!("Malformed `SOLUTIONS`!"))?;
+        let count = module[size..digits].parse::<usize>().wrap_err("Malformed size of `SOLUTIONS`!")?;
+        module.replace_range(size..digits, &(count + 1).to_string());
+    }
+
+    module.push_str(&PARTS_RS.replace("{day}", &day.to_string()).replace("{package}", package));
+
+    Ok(module)
+}
+
+const YEAR_RS: &str = r#"//! The solutions of {year}.
+
+use color_eyre::eyre::Result;
+use utils::timing;
+
+use super::Solution;
+
+pub static SOLUTIONS: [Solution; 0] = [
+];
+"#;
+
+const SOLUTION_RS: &str = r#"    Solution {
+        year: {year},
+        day: {day},
+        package: "{package}",
+        directory: "{directory}",
+        parts: &[day_{day}_part_1, day_{day}_part_2],
+        examples: &{package}::examples::EXAMPLES,
+        board: None,
+    },
+"#;
+
+const PARTS_RS: &str = r#"
+fn day_{day}_part_1(input: &str) -> Result<String> {
+    Ok(timing::span("part 1", || {package}::part_1(input))?.to_string())
+}
+
+fn day_{day}_part_2(input: &str) -> Result<String> {
+    Ok(timing::span("part 2", || {package}::part_2(input))?.to_string())
+}
+"#;
+
+const CARGO_TOML: &str = r#"[package]
+name = "{package}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+clap = { version = "4.6.7", features = ["derive"] }
+color-eyre = "0.6.2"
+utils = { path = "../../utils" }
+
+[build-dependencies]
+utils = { path = "../../utils" }
+
+[features]
+profile-alloc = []
+"#;
+
+const BUILD_RS: &str = r#"fn main() -> std::io::Result<()> {
+    utils::vault::bundle("input.txt")
+}
+"#;
+
+const LIB_RS: &str = r#"pub mod examples;
+
+use utils::error::Error;
+
+/// Solve part 1.
+///
+/// # Arguments
+///
+/// * `input` - The puzzle input.
+///
+/// # Returns
+///
+/// * `Result<u64, Error>` - The answer.
+///
+/// # Errors
+///
+/// * `Error` - If the input is malformed or has no answer.
+pub fn part_1(_input: &str) -> Result<u64, Error> {
+    Err(Error::Unsolvable("part 1 is not solved yet".to_string()))
+}
+
+/// Solve part 2.
+///
+/// # Arguments
+///
+/// * `input` - The puzzle input.
+///
+/// # Returns
+///
+/// * `Result<u64, Error>` - The answer.
+///
+/// # Errors
+///
+/// * `Error` - If the input is malformed or has no answer.
+pub fn part_2(_input: &str) -> Result<u64, Error> {
+    Err(Error::Unsolvable("part 2 is not solved yet".to_string()))
+}
+"#;
+
+const EXAMPLES_RS: &str = r#"//! The examples of {year} day {day} and their answers, from the puzzle page.
+//! Extract them with `aoc examples --year {year} --day {day} --page <page>` rather than editing them.
+
+use utils::example::Example;
+
+/// The examples of every part, in order, none until they are extracted.
+pub const EXAMPLES: [Example; 0] = [];
+"#;
+
+const MAIN_RS: &str = r#"use std::path::PathBuf;
+use std::process::ExitCode;
+use clap::Parser;
+use color_eyre::eyre::Result;
+use utils::report::{Format, Report};
+use utils::timing;
+
+#[cfg(feature = "profile-alloc")]
+#[global_allocator]
+static ALLOCATOR: utils::alloc::CountingAllocator = utils::alloc::CountingAllocator;
+
+#[derive(Debug, Parser)]
+struct Args {
+    /// Read the puzzle input from the given file instead of the bundled `input.txt`, which may be encrypted.
+    #[arg(long, value_name = "PATH")]
+    input: Option<PathBuf>,
+
+    /// Print a report in the given format (`json`, `csv` or `markdown`) instead of the usual output.
+    #[arg(long)]
+    report: Option<Format>,
+}
+
+fn main() -> ExitCode {
+    utils::error::run(solve)
+}
+
+fn solve() -> Result<()> {
+    let args = Args::parse();
+    let input = utils::error::read_input(args.input.as_deref(), utils::bundled_input!())?;
+    let input = input.as_ref();
+
+    let part_1 = timing::span("part 1", || {package}::part_1(input))?;
+    let part_2 = timing::span("part 2", || {package}::part_2(input))?;
+
+    let mut report = Report::new({year}, {day}, input, timing::take());
+    report.push(1, part_1);
+    report.push(2, part_2);
+
+    if let Some(format) = args.report {
+        print!("{}", report.render(format));
+
+        return Ok(());
+    }
+
+    println!("Part 1: {part_1}");
+    println!("Part 2: {part_2}");
+    print!("\n{}", report.timings);
+
+    Ok(())
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(directory(2023, 5), "y2023/day_05");
+        assert_eq!(package(2024, 12), "y2024_day_12");
+    }
+
+    #[test]
+    fn test_add_member() -> Result<()> {
+        let manifest = "[workspace]\nresolver = \"2\"\nmembers = [\n    \"aoc\",\n    \"day_4\",\n]\n";
+
+        assert_eq!(
+            add_member(manifest, "y2023/day_05")?,
+            "[workspace]\nresolver = \"2\"\nmembers = [\n    \"aoc\",\n    \"day_4\",\n    \"y2023/day_05\",\n]\n",
+        );
+        assert!(add_member("[workspace]\n", "y2023/day_05").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scaffold() -> Result<()> {
+        // Years far ahead, so no day the runner has solved is refused.
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(&root)?;
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\n    \"aoc\",\n]\n")?;
+        fs::create_dir_all(root.join("aoc/src/registry"))?;
+        fs::write(root.join("aoc/Cargo.toml"), "[dependencies]\nutils = { path = \"../utils\" }\n\n[features]\n")?;
+        let registry = "mod y9998;\n\nstatic YEARS: &[&[Solution]] = &[\n    &y9998::SOLUTIONS,\n];\n";
+        fs::write(root.join("aoc/src/registry/mod.rs"), registry)?;
+        fs::create_dir_all(root.join("aoc/src/registry/y9998"))?;
+        let year = concat!(
+            "pub static SOLUTIONS: [Solution; 1] = [\n",
+            "    Solution {\n        year: 9998,\n        day: 7,\n    },\n",
+            "];\n",
+            "\nfn day_7_part_1(input: &str) -> Result<String> {\n}\n",
+        );
+        fs::write(root.join("aoc/src/registry/y9998/mod.rs"), year)?;
+
+        let path = scaffold(&root, 9998, 5)?;
+
+        assert_eq!(path, root.join("y9998/day_05"));
+        let runner = fs::read_to_string(root.join("aoc/Cargo.toml"))?;
+        assert!(runner.contains("y9998_day_05 = { path = \"../y9998/day_05\" }\n\n[features]"));
+        let year = fs::read_to_string(root.join("aoc/src/registry/y9998/mod.rs"))?;
+        assert!(year.contains("[Solution; 2]"));
+        assert!(year.find("day: 5").zip(year.find("day: 7")).is_some_and(|(new, old)| new < old));
+        assert!(year.contains("y9998_day_05::part_2(input)"));
+        assert!(year.contains("examples: &y9998_day_05::examples::EXAMPLES,"));
+        assert!(fs::read_to_string(path.join("src/examples/mod.rs"))?.contains("EXAMPLES: [Example; 0] = [];"));
+
+        scaffold(&root, 9999, 1)?;
+        let registry = fs::read_to_string(root.join("aoc/src/registry/mod.rs"))?;
+        assert!(registry.starts_with("mod y9998;\nmod y9999;\n"));
+        assert!(registry.contains("    &y9998::SOLUTIONS,\n    &y9999::SOLUTIONS,\n];"));
+        assert!(fs::read_to_string(root.join("aoc/src/registry/y9999/mod.rs"))?.contains("//! The solutions of 9999."));
+        assert!(fs::read_to_string(root.join("Cargo.toml"))?.contains("\"y9998/day_05\""));
+        assert!(fs::read_to_string(path.join("Cargo.toml"))?.contains("name = \"y9998_day_05\""));
+        assert!(fs::read_to_string(path.join("src/main.rs"))?.contains("Report::new(9998, 5, input"));
+        assert!(scaffold(&root, 9998, 5).is_err());
+        assert!(scaffold(&root, 9998, 26).is_err());
+        // 2023 day 4 is already solved, and day 7 already registered, so neither is touched.
+        assert!(scaffold(&root, 2023, 4).is_err() && scaffold(&root, 9998, 7).is_err());
+        assert!(!root.join("y2023/day_04").exists() && !root.join("y9998/day_07").exists());
+
+        fs::remove_dir_all(root)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,470 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use clap::Args;
+use color_eyre::eyre::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use utils::render;
+use utils::report::Report;
+
+use crate::registry::{self, Board, Solution};
+use crate::run::{describe, parse_seconds, run_with_timeout};
+
+/// How long to wait for a key before drawing the results that came in meanwhile.
+const POLL: Duration = Duration::from_millis(50);
+
+/// Options for the terminal UI.
+#[derive(Debug, Args)]
+pub struct TuiArgs {
+    /// The root of the workspace, where the inputs are read from.
+    #[arg(long, default_value = ".")]
+    pub root: PathBuf,
+
+    /// Give up on a part that runs for longer than this many seconds.
+    #[arg(long, value_name = "SECONDS", default_value = "60", value_parser = parse_seconds)]
+    pub timeout: Duration,
+}
+
+/// Explore every solution in an interactive terminal UI, until `q` is pressed.
+///
+/// # Arguments
+///
+/// * `args` - The options of the UI.
+///
+/// # Returns
+///
+/// * `Result<()>` - Nothing.
+///
+/// # Errors
+///
+/// * `Report` - If the terminal cannot be drawn on or read from.
+pub fn tui(args: &TuiArgs) -> Result<()> {
+    let mut app = App::new(args.root.clone(), args.timeout);
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+
+    result
+}
+
+/// Which input the parts run on.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+enum Source {
+    Example,
+    Input,
+}
+
+impl Source {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Example => "example",
+            Self::Input => "real input",
+        }
+    }
+}
+
+/// A part to run on the worker, and the entry and input its result is for.
+struct Job {
+    entry: usize,
+    source: Source,
+    solution: &'static Solution,
+    part: u8,
+    input: Cow<'static, str>,
+}
+
+/// The result of a job, by entry and input.
+type Done = (usize, Source, Result<Report, String>);
+
+/// The state of the UI.
+struct App {
+    root: PathBuf,
+    /// Every part of every solution, in order.
+    entries: Vec<(&'static Solution, u8)>,
+    selected: usize,
+    source: Source,
+    /// The result of every part that has been run, by entry and input.
+    results: HashMap<(usize, Source), Result<Report, String>>,
+    /// The parts queued on the worker or running on it, by entry and input.
+    running: HashSet<(usize, Source)>,
+    jobs: Sender<Job>,
+    done: Receiver<Done>,
+    /// The board of the selected solution, while it is shown.
+    board: Option<Result<Board, String>>,
+    /// How far the board is scrolled down and right.
+    scroll: (u16, u16),
+    quit: bool,
+}
+
+impl App {
+    fn new(root: PathBuf, timeout: Duration) -> Self {
+        let entries = registry::solutions()
+            .flat_map(|solution| (1..=solution.parts.len()).map(move |part| (solution, part)))
+            .filter_map(|(solution, part)| Some((solution, u8::try_from(part).ok()?)))
+            .collect();
+
+        // Parts run one at a time on a worker, so the UI keeps responding and their timings do not skew each other.
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let (finished, done) = mpsc::channel();
+        thread::spawn(move || {
+            for job in queue {
+                let result = run_with_timeout(job.solution, job.part, &job.input, timeout);
+                if finished.send((job.entry, job.source, result.map_err(|report| describe(&report)))).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            root,
+            entries,
+            selected: 0,
+            source: Source::Input,
+            results: HashMap::new(),
+            running: HashSet::new(),
+            jobs,
+            done,
+            board: None,
+            scroll: (0, 0),
+            quit: false,
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        while !self.quit {
+            self.receive();
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(POLL)? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle(key.code);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collect the results of the parts that finished running since the last time.
+    fn receive(&mut self) {
+        while let Ok((entry, source, result)) = self.done.try_recv() {
+            self.running.remove(&(entry, source));
+            self.results.insert((entry, source), result);
+        }
+    }
+
+    fn handle(&mut self, key: KeyCode) {
+        let showing_board = self.board.is_some();
+
+        match key {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Esc if showing_board => self.board = None,
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') if showing_board => self.scroll.0 = self.scroll.0.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if showing_board => self.scroll.0 = self.scroll.0.saturating_add(1),
+            KeyCode::Left | KeyCode::Char('h') if showing_board => self.scroll.1 = self.scroll.1.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') if showing_board => self.scroll.1 = self.scroll.1.saturating_add(1),
+            KeyCode::PageUp if showing_board => self.scroll.0 = self.scroll.0.saturating_sub(20),
+            KeyCode::PageDown if showing_board => self.scroll.0 = self.scroll.0.saturating_add(20),
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.entries.len().saturating_sub(1));
+            }
+            KeyCode::Enter | KeyCode::Char('r') => self.run_entry(self.selected),
+            KeyCode::Char('a') => (0..self.entries.len()).for_each(|entry| self.run_entry(entry)),
+            KeyCode::Tab | KeyCode::Char('e') => {
+                self.source = match self.source {
+                    Source::Example => Source::Input,
+                    Source::Input => Source::Example,
+                };
+                if showing_board {
+                    self.show_board();
+                }
+            }
+            KeyCode::Char('b') if showing_board => self.board = None,
+            KeyCode::Char('b') => self.show_board(),
+            _ => {}
+        }
+    }
+
+    fn input(&self, solution: &Solution, part: u8) -> Result<Cow<'static, str>, String> {
+        match self.source {
+            Source::Example => solution
+                .example(part)
+                .map(Cow::Borrowed)
+                .ok_or_else(|| format!("{solution} has no example")),
+            Source::Input => solution.input(&self.root).map_err(|report| describe(&report)),
+        }
+    }
+
+    /// Queue a part on the worker, unless it is already queued or running.
+    fn run_entry(&mut self, entry: usize) {
+        let Some(&(solution, part)) = self.entries.get(entry) else {
+            return;
+        };
+        let source = self.source;
+        if self.running.contains(&(entry, source)) {
+            return;
+        }
+
+        match self.input(solution, part) {
+            Ok(input) => match self.jobs.send(Job { entry, source, solution, part, input }) {
+                Ok(()) => _ = self.running.insert((entry, source)),
+                Err(_) => _ = self.results.insert((entry, source), Err(String::from("the worker has stopped"))),
+            },
+            Err(error) => _ = self.results.insert((entry, source), Err(error)),
+        }
+    }
+
+    fn show_board(&mut self) {
+        let Some(&(solution, part)) = self.entries.get(self.selected) else {
+            return;
+        };
+
+        self.board = Some(match solution.board {
+            Some(board) => {
+                self.input(solution, part).and_then(|input| board(&input).map_err(|report| describe(&report)))
+            }
+            None => Err(format!("{solution} is not on a grid")),
+        });
+        self.scroll = (0, 0);
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main, help] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let [list, details] = Layout::horizontal([Constraint::Length(36), Constraint::Fill(1)]).areas(main);
+
+        self.draw_list(frame, list);
+        match &self.board {
+            Some(board) => self.draw_board(frame, details, board),
+            None => self.draw_details(frame, details),
+        }
+
+        let status = match self.running.len() {
+            0 => String::new(),
+            running => format!("Running {running} parts…  "),
+        };
+        let help_text = format!(
+            "{status}↑↓ select  enter run  a run all  tab {}  b board  q quit",
+            match self.source {
+                Source::Example => "real input",
+                Source::Input => "example",
+            }
+        );
+        frame.render_widget(Line::from(help_text).dim(), help);
+    }
+
+    fn draw_list(&self, frame: &mut Frame, area: Rect) {
+        let items = self.entries.iter().enumerate().map(|(entry, (solution, part))| {
+            let result = match self.results.get(&(entry, self.source)) {
+                _ if self.running.contains(&(entry, self.source)) => "running…".dim(),
+                Some(Ok(report)) => match report.parts.first() {
+                    Some(part) if part.is_solved() => part.answer.clone().green(),
+                    Some(part) => part.status.to_string().red(),
+                    None => "".into(),
+                },
+                Some(Err(_)) => "failed".red(),
+                None => "".into(),
+            };
+
+            ListItem::new(Line::from(vec![Span::raw(format!("{solution} part {part}  ")), result]))
+        });
+
+        let list = List::new(items)
+            .block(Block::bordered().title(format!(" Solutions · {} ", self.source.name())))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn draw_details(&self, frame: &mut Frame, area: Rect) {
+        let Some(&(solution, part)) = self.entries.get(self.selected) else {
+            return;
+        };
+        let [summary, spans] = Layout::vertical([Constraint::Length(6), Constraint::Fill(1)]).areas(area);
+        let title = format!(" {solution} part {part} · {} ", self.source.name());
+
+        let key = (self.selected, self.source);
+        let (lines, timings) = match self.results.get(&key) {
+            _ if self.running.contains(&key) => (vec![Line::from("Running…")], String::new()),
+            None => (vec![Line::from("Not run yet, press enter to run it.")], String::new()),
+            Some(Err(error)) => (vec![Line::from(error.as_str().red())], String::new()),
+            Some(Ok(report)) => {
+                let Some(result) = report.parts.first() else {
+                    return;
+                };
+                let memory = result.alloc.map_or_else(
+                    || "not counted, build with `--features profile-alloc`".to_string(),
+                    |alloc| alloc.to_string(),
+                );
+
+                let answer = if result.is_solved() {
+                    Line::from(vec!["Answer: ".into(), result.answer.clone().bold()])
+                } else {
+                    Line::from(vec!["Status: ".into(), result.status.to_string().red()])
+                };
+                let lines = vec![
+                    answer,
+                    Line::from(format!("Parse:  {:.2?}", result.parse)),
+                    Line::from(format!("Solve:  {:.2?}", result.solve)),
+                    Line::from(format!("Memory: {memory}")),
+                ];
+
+                (lines, report.timings.to_string())
+            }
+        };
+
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(title)), summary);
+        frame.render_widget(
+            Paragraph::new(timings).wrap(Wrap { trim: false }).block(Block::bordered().title(" Spans ")),
+            spans,
+        );
+    }
+
+    fn draw_board(&self, frame: &mut Frame, area: Rect, board: &Result<Board, String>) {
+        let Some(&(solution, _)) = self.entries.get(self.selected) else {
+            return;
+        };
+        let title = format!(" {solution} · {} · arrows scroll, b closes ", self.source.name());
+        let block = Block::bordered().title(title);
+
+        let board = match board {
+            Ok(board) => Paragraph::new(board_lines(board)).scroll(self.scroll),
+            Err(error) => Paragraph::new(error.as_str().red()),
+        };
+        frame.render_widget(board.block(block), area);
+    }
+}
+
+/// Draw a board as a line of styled cells per row.
+fn board_lines(board: &Board) -> Vec<Line<'static>> {
+    board
+        .cells
+        .rows()
+        .zip(board.styles.rows())
+        .map(|(cells, styles)| {
+            let spans = cells.iter().zip(styles).map(|(&cell, style)| match style {
+                Some(style) => {
+                    let span = Span::styled(cell.to_string(), Style::new().fg(color(style.color)));
+                    if style.bold { span.bold() } else { span }
+                }
+                None => Span::raw(cell.to_string()).dim(),
+            });
+
+            Line::from(spans.collect::<Vec<_>>())
+        })
+        .collect()
+}
+
+fn color(color: render::Color) -> Color {
+    match color {
+        render::Color::Red => Color::Red,
+        render::Color::Green => Color::Green,
+        render::Color::Yellow => Color::Yellow,
+        render::Color::Blue => Color::Blue,
+        render::Color::Magenta => Color::Magenta,
+        render::Color::Cyan => Color::Cyan,
+        render::Color::Gray => Color::DarkGray,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    use super::*;
+
+    fn app() -> App {
+        App::new(PathBuf::from("."), Duration::from_secs(60))
+    }
+
+    /// Wait for every queued part to finish.
+    fn wait(app: &mut App) {
+        while !app.running.is_empty() {
+            thread::sleep(Duration::from_millis(10));
+            app.receive();
+        }
+    }
+
+    fn screen(app: &App) -> Result<String> {
+        let mut terminal = Terminal::new(TestBackend::new(120, 30))?;
+        terminal.draw(|frame| app.draw(frame))?;
+
+        Ok(terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect())
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut app = app();
+        let parts = registry::solutions().map(|solution| solution.parts.len()).sum::<usize>();
+        assert_eq!(app.entries.len(), parts);
+
+        app.handle(KeyCode::Up);
+        assert_eq!(app.selected, 0);
+        (0..parts + 2).for_each(|_| app.handle(KeyCode::Down));
+        assert_eq!(app.selected, parts - 1);
+
+        app.handle(KeyCode::Tab);
+        assert_eq!(app.source, Source::Example);
+        app.handle(KeyCode::Char('a'));
+        wait(&mut app);
+        assert!((0..parts).all(|entry| app.results.contains_key(&(entry, Source::Example))));
+
+        app.handle(KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn test_run_example() -> Result<()> {
+        let mut app = app();
+        app.handle(KeyCode::Tab);
+        app.run_entry(1);
+        app.selected = 1;
+        assert!(screen(&app)?.contains("Running…"));
+        wait(&mut app);
+
+        let report = app.results.get(&(1, Source::Example)).and_then(|result| result.as_ref().ok());
+        assert_eq!(report.and_then(|report| report.parts.first()).map(|part| part.answer.as_str()), Some("281"));
+
+        let screen = screen(&app)?;
+        assert!(screen.contains("Answer: 281"));
+        assert!(screen.contains("2023 day 1 part 2 · example"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_board() -> Result<()> {
+        let mut app = app();
+        app.handle(KeyCode::Tab);
+
+        app.handle(KeyCode::Char('b'));
+        assert!(app.board.as_ref().is_some_and(Result::is_err));
+        app.handle(KeyCode::Esc);
+        assert!(app.board.is_none() && !app.quit);
+
+        app.selected = 4;
+        app.handle(KeyCode::Char('b'));
+        let lines = app.board.as_ref().and_then(|board| board.as_ref().ok()).map(board_lines);
+        assert_eq!(lines.as_ref().map(Vec::len), Some(10));
+
+        app.handle(KeyCode::Down);
+        assert_eq!((app.selected, app.scroll), (4, (1, 0)));
+        assert!(screen(&app)?.contains("..35..633."));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+use color_eyre::eyre::{bail, ensure, eyre, Result, WrapErr};
+use utils::vault::{self, Key, EXTENSION};
+
+/// The name of the plain input of every day.
+const INPUT: &str = "input.txt";
+
+/// Manage the encrypted inputs of every day.
+#[derive(Debug, Subcommand)]
+pub enum VaultCommand {
+    /// Generate a new key and write it to a key file.
+    Keygen {
+        /// The key file, `~/.config/aoc/input.key` by default.
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+        /// Overwrite an existing key file, losing access to every input encrypted with it.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Encrypt the `input.txt` of every day into `input.txt.enc`, with the configured key.
+    Encrypt {
+        /// The root of the workspace.
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// Decrypt the `input.txt.enc` of every day back into `input.txt`, with the configured key.
+    Decrypt {
+        /// The root of the workspace.
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// Re-encrypt the `input.txt.enc` of every day from the configured key to a new one.
+    Rotate {
+        /// The file holding the new key, e.g. from `aoc vault keygen --output`.
+        #[arg(long, value_name = "PATH")]
+        new_key: PathBuf,
+        /// The root of the workspace.
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+}
+
+/// Run a vault command, printing every file it writes.
+///
+/// # Arguments
+///
+/// * `command` - The command to run.
+///
+/// # Returns
+///
+/// * `Result<()>` - Nothing.
+///
+/// # Errors
+///
+/// * `Report` - If a key cannot be loaded, or an input cannot be read, decrypted or written.
+pub fn run(command: &VaultCommand) -> Result<()> {
+    let written = match command {
+        VaultCommand::Keygen { output, force } => {
+            let path = match output {
+                Some(path) => path.clone(),
+                None => vault::default_key_path().ok_or_else(|| eyre!("There is no home directory, pass `--output`!"))?,
+            };
+
+            vec![keygen(&path, *force)?]
+        }
+        VaultCommand::Encrypt { root } => encrypt_all(root, &Key::load()?)?,
+        VaultCommand::Decrypt { root } => decrypt_all(root, &Key::load()?)?,
+        VaultCommand::Rotate { new_key, root } => rotate_all(root, &Key::load()?, &Key::read(new_key)?)?,
+    };
+
+    for path in written {
+        println!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Generate a new key and write it to a file that only the owner can read.
+fn keygen(path: &Path, force: bool) -> Result<PathBuf> {
+    ensure!(force || !path.exists(), "{} already exists, pass `--force` to overwrite it!", path.display());
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{}\n", Key::generate().to_hex()))
+        .wrap_err_with(|| format!("Failed to write {}!", path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+
+    Ok(path.to_path_buf())
+}
+
+/// Find the subdirectories of a directory whose names match a predicate.
+fn subdirectories(directory: &Path, matches: impl Fn(&str) -> bool) -> Result<Vec<PathBuf>> {
+    Ok(fs::read_dir(directory)
+        .wrap_err_with(|| format!("Failed to read {}!", directory.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir() && path.file_name().and_then(|name| name.to_str()).is_some_and(&matches))
+        .collect())
+}
+
+/// Find the directory of every day in the workspace, e.g. `day_4` for 2023 or `y2023/day_05`.
+fn days(root: &Path) -> Result<Vec<PathBuf>> {
+    let is_year = |name: &str| name.strip_prefix('y').is_some_and(|year| year.parse::<u16>().is_ok());
+    let is_day = |name: &str| name.starts_with("day_");
+
+    let mut days = subdirectories(root, is_day)?;
+    for year in subdirectories(root, is_year)? {
+        days.extend(subdirectories(&year, is_day)?);
+    }
+    days.sort();
+
+    if days.is_empty() {
+        bail!("There are no days in {}, pass the root of the workspace with `--root`!", root.display());
+    }
+
+    Ok(days)
+}
+
+fn encrypted_path(day: &Path) -> PathBuf {
+    day.join(format!("{INPUT}.{EXTENSION}"))
+}
+
+fn read(path: &Path) -> Result<Vec<u8>> {
+    fs::read(path).wrap_err_with(|| format!("Failed to read {}!", path.display()))
+}
+
+fn write(path: PathBuf, contents: &[u8]) -> Result<PathBuf> {
+    fs::write(&path, contents).wrap_err_with(|| format!("Failed to write {}!", path.display()))?;
+
+    Ok(path)
+}
+
+/// Encrypt the plain input of every day that has one.
+fn encrypt_all(root: &Path, key: &Key) -> Result<Vec<PathBuf>> {
+    days(root)?
+        .into_iter()
+        .filter(|day| day.join(INPUT).exists())
+        .map(|day| {
+            let plaintext = read(&day.join(INPUT))?;
+            ensure!(!vault::is_encrypted(&plaintext), "{} is already encrypted!", day.join(INPUT).display());
+
+            write(encrypted_path(&day), &vault::encrypt(key, &plaintext))
+        })
+        .collect()
+}
+
+/// Decrypt the encrypted input of every day that has one.
+fn decrypt_all(root: &Path, key: &Key) -> Result<Vec<PathBuf>> {
+    let plaintexts = decrypt_days(root, key)?;
+
+    plaintexts.into_iter().map(|(day, plaintext)| write(day.join(INPUT), &plaintext)).collect()
+}
+
+/// Re-encrypt the encrypted input of every day with a new key.
+fn rotate_all(root: &Path, old: &Key, new: &Key) -> Result<Vec<PathBuf>> {
+    ensure!(old != new, "The new key is the same as the current one!");
+
+    // Decrypt every input before writing any, so a wrong key never leaves the inputs encrypted with two keys.
+    let plaintexts = decrypt_days(root, old)?;
+
+    plaintexts
+        .into_iter()
+        .map(|(day, plaintext)| write(encrypted_path(&day), &vault::encrypt(new, &plaintext)))
+        .collect()
+}
+
+fn decrypt_days(root: &Path, key: &Key) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    days(root)?
+        .into_iter()
+        .filter(|day| encrypted_path(day).exists())
+        .map(|day| {
+            let path = encrypted_path(&day);
+            let plaintext =
+                vault::decrypt(key, &read(&path)?).wrap_err_with(|| format!("Failed to decrypt {}!", path.display()))?;
+
+            Ok((day, plaintext))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create a workspace with two days of 2023 and one in the year-aware layout, in a fresh temporary directory.
+    fn workspace(name: &str) -> Result<PathBuf> {
+        let root = std::env::temp_dir().join(format!("aoc-vault-{}-{name}", std::process::id()));
+        if root.exists() {
+            fs::remove_dir_all(&root)?;
+        }
+
+        for (day, input) in [("day_1", "1abc2\n"), ("day_2", "Game 1: 3 blue\n"), ("y2023/day_05", "79 14 55 13\n")] {
+            fs::create_dir_all(root.join(day))?;
+            fs::write(root.join(day).join(INPUT), input)?;
+        }
+        fs::create_dir_all(root.join("utils"))?;
+        fs::create_dir_all(root.join("yarn"))?;
+
+        Ok(root)
+    }
+
+    #[test]
+    fn test_encrypt_and_decrypt() -> Result<()> {
+        let root = workspace("encrypt")?;
+        let key = Key::generate();
+
+        let encrypted = ["day_1", "day_2", "y2023/day_05"].map(|day| encrypted_path(&root.join(day))).to_vec();
+        assert_eq!(encrypt_all(&root, &key)?, encrypted);
+        assert!(vault::is_encrypted(&fs::read(encrypted_path(&root.join("day_2")))?));
+
+        fs::remove_file(root.join("day_2").join(INPUT))?;
+        assert!(decrypt_all(&root, &Key::generate()).is_err());
+        assert_eq!(decrypt_all(&root, &key)?.len(), 3);
+        assert_eq!(fs::read_to_string(root.join("day_2").join(INPUT))?, "Game 1: 3 blue\n");
+
+        fs::remove_dir_all(root)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rotate() -> Result<()> {
+        let root = workspace("rotate")?;
+        let (old, new) = (Key::generate(), Key::generate());
+        encrypt_all(&root, &old)?;
+
+        assert!(rotate_all(&root, &new, &old).is_err());
+        assert!(rotate_all(&root, &old, &old).is_err());
+        assert_eq!(rotate_all(&root, &old, &new)?.len(), 3);
+
+        let encrypted = fs::read(encrypted_path(&root.join("day_1")))?;
+        assert!(vault::decrypt(&old, &encrypted).is_err());
+        assert_eq!(vault::decrypt(&new, &encrypted)?, b"1abc2\n");
+
+        fs::remove_dir_all(root)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_keygen() -> Result<()> {
+        let root = workspace("keygen")?;
+        let path = root.join("keys").join("input.key");
+
+        keygen(&path, false)?;
+        let key = Key::read(&path)?;
+
+        assert!(keygen(&path, false).is_err());
+        assert_eq!(Key::read(&path)?, key);
+        keygen(&path, true)?;
+        assert_ne!(Key::read(&path)?, key);
+
+        fs::remove_dir_all(root)?;
+
+        Ok(())
+    }
+}
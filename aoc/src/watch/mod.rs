@@ -0,0 +1,211 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use clap::Args;
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use notify::{Event, RecursiveMode, Watcher};
+use utils::report::Report;
+
+use crate::registry::{self, Solution};
+
+/// How long to wait for more changes after one, so that saving several files reruns the day once.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Options for watching a day.
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    /// The year of the day, the latest year by default.
+    #[arg(long)]
+    pub year: Option<u16>,
+
+    /// The day to watch.
+    #[arg(long)]
+    pub day: u8,
+
+    /// The root of the workspace.
+    #[arg(long, default_value = ".")]
+    pub root: PathBuf,
+}
+
+/// Watch the sources and the input of a day, and rerun its tests and its input whenever they change.
+///
+/// # Arguments
+///
+/// * `args` - The day to watch.
+///
+/// # Returns
+///
+/// * `Result<()>` - Nothing, since this only returns on an error.
+///
+/// # Errors
+///
+/// * `Report` - If there is no such day, or its directory cannot be watched.
+pub fn watch(args: &WatchArgs) -> Result<()> {
+    let year = args.year.unwrap_or_else(registry::latest_year);
+    let solution =
+        registry::find(year, args.day).ok_or_else(|| eyre!("There is no solution to {year} day {}!", args.day))?;
+    // Events carry absolute paths, so watch the absolute directory to recognise them.
+    let directory = solution.directory(&args.root).canonicalize()?;
+
+    let (sender, receiver) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    watcher.watch(&directory.join("src"), RecursiveMode::Recursive)?;
+    watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+
+    println!("Watching {} for changes to {solution}, press Ctrl+C to stop.", directory.display());
+    let mut previous = rerun(solution, &args.root, None);
+
+    loop {
+        let event = receiver.recv()?;
+        if !is_relevant(&event?, &directory) {
+            continue;
+        }
+
+        // Drain the rest of the burst, e.g. an editor writing a file and then renaming it.
+        while receiver.recv_timeout(DEBOUNCE).is_ok() {}
+
+        println!("\n── {solution} changed, rerunning ──");
+        previous = rerun(solution, &args.root, previous.as_ref()).or(previous);
+    }
+}
+
+/// Check whether an event is about a source file, the input or the manifest of a day.
+fn is_relevant(event: &Event, directory: &Path) -> bool {
+    if event.kind.is_access() {
+        return false;
+    }
+
+    event.paths.iter().filter_map(|path| path.strip_prefix(directory).ok()).any(|relative| {
+        relative.starts_with("src")
+            || relative == Path::new("input.txt")
+            || relative == Path::new("input.txt.enc")
+            || relative == Path::new("Cargo.toml")
+    })
+}
+
+/// Run the tests of a day, then the day on its input, and print how the answers and timings changed.
+///
+/// Any failure is printed rather than returned, so watching goes on until the next change.
+fn rerun(solution: &Solution, root: &Path, previous: Option<&Report>) -> Option<Report> {
+    match run_tests(solution, root).and_then(|()| run_input(solution, root)) {
+        Ok(report) => {
+            print!("{}", diff(previous, &report));
+
+            Some(report)
+        }
+        Err(report) => {
+            println!("{report}");
+
+            None
+        }
+    }
+}
+
+fn cargo(root: &Path) -> Command {
+    let mut command = Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command.current_dir(root);
+
+    command
+}
+
+fn run_tests(solution: &Solution, root: &Path) -> Result<()> {
+    let status = cargo(root).args(["test", "--quiet", "--package", solution.package]).status()?;
+
+    status.success().then_some(()).ok_or_else(|| eyre!("The tests of {solution} failed!"))
+}
+
+fn run_input(solution: &Solution, root: &Path) -> Result<Report> {
+    let output = cargo(root)
+        .args(["run", "--quiet", "--release", "--package", solution.package, "--", "--report", "json"])
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(eyre!("{solution} failed on its input!"));
+    }
+
+    serde_json::from_slice(&output.stdout).wrap_err_with(|| format!("{solution} did not print a JSON report!"))
+}
+
+/// Describe the answers and timings of a run, and how they changed since the previous run.
+///
+/// # Arguments
+///
+/// * `previous` - The report of the previous run, if there was one.
+/// * `current` - The report of this run.
+///
+/// # Returns
+///
+/// * `String` - A line per part.
+#[must_use]
+pub fn diff(previous: Option<&Report>, current: &Report) -> String {
+    let mut diff = String::new();
+
+    for part in &current.parts {
+        let before = previous.and_then(|previous| previous.parts.iter().find(|before| before.part == part.part));
+        let time = part.parse + part.solve;
+
+        let answer = match before {
+            Some(before) if before.answer != part.answer => format!("{} → {} (changed)", before.answer, part.answer),
+            _ => part.answer.clone(),
+        };
+
+        let _ = write!(diff, "Part {}: {answer}   {time:.2?}", part.part);
+        if let Some(before) = before {
+            let before = before.parse + before.solve;
+            let change = (time.as_secs_f64() / before.as_secs_f64().max(f64::EPSILON) - 1.0) * 100.0;
+            let _ = write!(diff, " (was {before:.2?}, {change:+.1}%)");
+        }
+        diff.push('\n');
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use notify::event::{AccessKind, CreateKind, EventKind, ModifyKind};
+
+    use super::*;
+    use utils::timing::Timings;
+
+    fn report(answers: [&str; 2], solve_micros: u64) -> Report {
+        let mut report = Report::new(2023, 3, "467..114..", Timings::default());
+        for (part, answer) in (1..).zip(answers) {
+            report.push(part, answer);
+            report.parts[usize::from(part) - 1].solve = Duration::from_micros(solve_micros);
+        }
+
+        report
+    }
+
+    #[test]
+    fn test_diff() {
+        let first = report(["4361", "0"], 100);
+        assert_eq!(diff(None, &first), "Part 1: 4361   100.00µs\nPart 2: 0   100.00µs\n");
+
+        let second = report(["4361", "467835"], 50);
+        assert_eq!(
+            diff(Some(&first), &second),
+            "Part 1: 4361   50.00µs (was 100.00µs, -50.0%)\n\
+             Part 2: 0 → 467835 (changed)   50.00µs (was 100.00µs, -50.0%)\n",
+        );
+    }
+
+    #[test]
+    fn test_is_relevant() {
+        let directory = Path::new("/workspace/day_3");
+        let event = |kind, path: &str| Event::new(kind).add_path(PathBuf::from(path));
+        let modify = EventKind::Modify(ModifyKind::Any);
+
+        assert!(is_relevant(&event(modify, "/workspace/day_3/src/lib.rs"), directory));
+        assert!(is_relevant(&event(EventKind::Create(CreateKind::File), "/workspace/day_3/input.txt"), directory));
+        assert!(is_relevant(&event(modify, "/workspace/day_3/Cargo.toml"), directory));
+        assert!(!is_relevant(&event(modify, "/workspace/day_3/.lib.rs.swp"), directory));
+        assert!(!is_relevant(&event(modify, "/workspace/day_4/src/lib.rs"), directory));
+        assert!(!is_relevant(&event(EventKind::Access(AccessKind::Any), "/workspace/day_3/src/lib.rs"), directory));
+    }
+}
@@ -0,0 +1,23 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_1::examples::{PART_1, PART_2};
+
+// Day 1 scans every line as it goes, so there is no separate parse step to benchmark.
+fn bench_parts(c: &mut Criterion) {
+    let input = utils::error::read_input(None, utils::bundled_input!()).expect("Failed to read the input!");
+    let input = input.as_ref();
+
+    let mut group = c.benchmark_group("part 1");
+    group.bench_function("example", |b| b.iter(|| day_1::part_1::calibrate_lines(black_box(PART_1.input))));
+    group.bench_function("input", |b| b.iter(|| day_1::part_1::calibrate_lines(black_box(input))));
+    group.finish();
+
+    let mut group = c.benchmark_group("part 2");
+    group.bench_function("example", |b| b.iter(|| day_1::part_2::calibrate_lines(black_box(PART_2.input))));
+    group.bench_function("input", |b| b.iter(|| day_1::part_2::calibrate_lines(black_box(input))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_parts);
+criterion_main!(benches);
@@ -0,0 +1,3 @@
+fn main() -> std::io::Result<()> {
+    utils::vault::bundle("input.txt")
+}
@@ -0,0 +1,30 @@
+//! The examples of 2023 day 1 and their answers, from the puzzle page.
+//! Regenerate them with `aoc examples --year 2023 --day 1 --page <page>` rather than editing them.
+
+use utils::example::Example;
+
+/// The example of part 1.
+pub const PART_1: Example = Example {
+    input: "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet",
+    answer: "142",
+};
+
+/// The example of part 2.
+pub const PART_2: Example = Example {
+    input: "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen",
+    answer: "281",
+};
+
+/// The examples of every part, in order.
+pub const EXAMPLES: [Example; 2] = [PART_1, PART_2];
@@ -1,5 +1,10 @@
+pub mod examples;
+
 #[cfg(feature = "part_1")]
 pub mod part_1;
 
 #[cfg(feature = "part_2")]
 pub mod part_2;
+
+#[cfg(all(test, feature = "part_1", feature = "part_2"))]
+mod properties;
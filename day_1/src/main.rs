@@ -1,9 +1,50 @@
-fn main() {
-    let input = include_str!("../input.txt");
+use std::path::PathBuf;
+use std::process::ExitCode;
+use clap::Parser;
+use color_eyre::eyre::Result;
+use utils::report::{Format, Report};
+use utils::timing;
 
-    let (result, duration) = utils::time_it(|| day_1::part_1::calibrate_lines(input));
-    println!("Part 1: {result} (took {duration:#?})");
+#[cfg(feature = "profile-alloc")]
+#[global_allocator]
+static ALLOCATOR: utils::alloc::CountingAllocator = utils::alloc::CountingAllocator;
 
-    let (result, duration) = utils::time_it(|| day_1::part_2::calibrate_lines(input));
-    println!("Part 2: {result} (took {duration:#?})");
+#[derive(Debug, Parser)]
+struct Args {
+    /// Read the puzzle input from the given file instead of the bundled `input.txt`, which may be encrypted.
+    #[arg(long, value_name = "PATH")]
+    input: Option<PathBuf>,
+
+    /// Print a report in the given format (`json`, `csv` or `markdown`) instead of the usual output.
+    #[arg(long)]
+    report: Option<Format>,
+}
+
+fn main() -> ExitCode {
+    utils::error::run(solve)
+}
+
+fn solve() -> Result<()> {
+    let args = Args::parse();
+    let input = utils::error::read_input(args.input.as_deref(), utils::bundled_input!())?;
+    let input = input.as_ref();
+
+    let part_1 = timing::span("part 1", || day_1::part_1::calibrate_lines(input));
+    let part_2 = timing::span("part 2", || day_1::part_2::calibrate_lines(input));
+
+    let mut report = Report::new(2023, 1, input, timing::take());
+    report.push(1, part_1);
+    report.push(2, part_2);
+
+    if let Some(format) = args.report {
+        print!("{}", report.render(format));
+
+        return Ok(());
+    }
+
+    println!("Part 1: {part_1}");
+    println!("Part 2: {part_2}");
+    print!("\n{}", report.timings);
+
+    Ok(())
 }
@@ -29,7 +29,7 @@ pub fn calibrate_lines(input: &str) -> u32 {
         .sum()
 }
 
-fn get_digits(input: &str) -> Option<(u32, u32)> {
+pub(crate) fn get_digits(input: &str) -> Option<(u32, u32)> {
     /*
      We need to get the first and the last digit, ignore the rest.
      These digits might not be at the start and end of the string, so we need to find them.
@@ -58,14 +58,9 @@ mod tests {
 
     #[test]
     fn test_calibrate_lines() {
-        let input = r"
-            1abc2
-            pqr3stu8vwx
-            a1b2c3d4e5f
-            treb7uchet
-        ".trim();
+        let example = crate::examples::PART_1;
 
-        assert_eq!(calibrate_lines(input), 142);
+        assert_eq!(calibrate_lines(example.input).to_string(), example.answer);
     }
 
     #[test]
@@ -1,4 +1,6 @@
-static STRINGIFIED_NUMBERS: [(&str, u32); 10] = [
+use utils::parse::{self, Input};
+
+pub(crate) static STRINGIFIED_NUMBERS: [(&str, u32); 10] = [
     ("zero", 0),
     ("one", 1),
     ("two", 2),
@@ -45,76 +47,41 @@ pub fn calibrate_lines(input: &str) -> u32 {
         .sum()
 }
 
-fn get_digits(input: &str) -> Option<(u32, u32)> {
+pub(crate) fn get_digits(input: &str) -> Option<(u32, u32)> {
     let mut first = None;
     let mut last = None;
 
-    let mut seen_chars = 0;
-    for c in input.chars() {
-        // Keep a log of how many characters we've seen so far, so we can skip them later.
-        seen_chars += 1;
-
-        if let Some(digit) = c.to_digit(10) {
+    let spelled = parse::one_of(&STRINGIFIED_NUMBERS);
+    let mut rest = Input::new(input);
+    while let Some(c) = rest.rest().chars().next() {
+        /*
+         If the character is not a digit, try parsing a stringified number starting at it.
+         Stringified numbers can overlap (e.g. "twone"), so only ever move on by a single character.
+         */
+        let digit = c.to_digit(10).or_else(|| spelled(rest).ok().map(|(number, _)| number));
+        if let Some(digit) = digit {
             if first.is_none() {
                 first = Some(digit);
             }
 
             last = Some(digit);
-
-            continue;
         }
 
-        /*
-         If the character is not a digit, try parsing it as a stringified number.
-         The stringified number might be more than one character long, so we need to check the next characters as well.
-         */
-        let mut number = String::new();
-        for c in input.chars().skip(seen_chars - 1) {
-            number.push(c);
-
-            // If the stringified number is successfully parsed, we can stop checking the next characters and move on.
-            if let Some(number) = get_number_from_string(&number) {
-                if first.is_none() {
-                    first = Some(number);
-                }
-
-                last = Some(number);
-
-                break;
-            }
-        }
+        rest = rest.advance(c.len_utf8());
     }
 
     first.map(|f| (f, last.unwrap_or(f)))
 }
 
-fn get_number_from_string(input: &str) -> Option<u32> {
-    for (stringified_number, number) in &STRINGIFIED_NUMBERS {
-        if input == *stringified_number {
-            return Some(*number);
-        }
-    }
-
-    None
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_calibrate_lines() {
-        let input = r"
-            two1nine
-            eightwothree
-            abcone2threexyz
-            xtwone3four
-            4nineeightseven2
-            zoneight234
-            7pqrstsixteen
-        ".trim();
+        let example = crate::examples::PART_2;
 
-        assert_eq!(calibrate_lines(input), 281);
+        assert_eq!(calibrate_lines(example.input).to_string(), example.answer);
     }
 
     #[test]
@@ -0,0 +1,118 @@
+//! Property-based tests comparing the digit scanning of part 1 and part 2.
+
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use crate::part_2::STRINGIFIED_NUMBERS;
+use crate::{part_1, part_2};
+
+/// A piece of a generated calibration line that stands for a digit.
+#[derive(Debug, Clone, Copy)]
+enum Token {
+    Digit(u32),
+    Word(usize),
+}
+
+impl Token {
+    fn value(self) -> u32 {
+        match self {
+            Self::Digit(digit) => digit,
+            Self::Word(index) => STRINGIFIED_NUMBERS[index].1,
+        }
+    }
+
+    fn push_to(self, line: &mut String) {
+        match self {
+            Self::Digit(digit) => line.push(char::from_digit(digit, 10).unwrap_or('0')),
+            Self::Word(index) => line.push_str(STRINGIFIED_NUMBERS[index].0),
+        }
+    }
+}
+
+/// Pairs of number words that share letters, and the two digits they stand for.
+const OVERLAPS: [(&str, u32, u32); 9] = [
+    ("zerone", 0, 1),
+    ("oneight", 1, 8),
+    ("twone", 2, 1),
+    ("threeight", 3, 8),
+    ("fiveight", 5, 8),
+    ("sevenine", 7, 9),
+    ("eightwo", 8, 2),
+    ("eighthree", 8, 3),
+    ("nineight", 9, 8),
+];
+
+/// Noise that cannot be part of a number word, since none of these letters appear in one.
+const NOISE: &str = "[abcdjklmpqy]{1,4}";
+
+/// Like [`NOISE`], but possibly empty.
+const OPTIONAL_NOISE: &str = "[abcdjklmpqy]{0,4}";
+
+fn token() -> impl Strategy<Value = Token> {
+    prop_oneof![(0_u32..10).prop_map(Token::Digit), (0..STRINGIFIED_NUMBERS.len()).prop_map(Token::Word)]
+}
+
+fn contains_word(line: &str) -> bool {
+    STRINGIFIED_NUMBERS.iter().any(|(word, _)| line.contains(word))
+}
+
+/// The first and last of the given digits, like `get_digits` returns them.
+fn first_and_last(digits: impl IntoIterator<Item = u32>) -> Option<(u32, u32)> {
+    digits.into_iter().fold(None, |found, digit| Some((found.map_or(digit, |(first, _)| first), digit)))
+}
+
+proptest! {
+    #[test]
+    fn part_2_agrees_with_part_1_without_words(line in "[0-9a-z]{0,40}") {
+        prop_assume!(!contains_word(&line));
+
+        prop_assert_eq!(part_2::get_digits(&line), part_1::get_digits(&line));
+    }
+
+    #[test]
+    fn part_1_ignores_noise(
+        line in "[0-9a-z]{0,40}",
+        noise in prop::collection::vec((any::<prop::sample::Index>(), "[a-z!?. ]"), 0..10),
+    ) {
+        let mut noisy = line.clone();
+        for (index, character) in &noise {
+            noisy.insert_str(index.index(noisy.len() + 1), character);
+        }
+
+        prop_assert_eq!(part_1::get_digits(&noisy), part_1::get_digits(&line));
+    }
+
+    #[test]
+    fn part_2_ignores_noise_between_tokens(
+        tokens in prop::collection::vec(token(), 0..10),
+        separators in prop::collection::vec(NOISE, 11),
+        leading in OPTIONAL_NOISE,
+    ) {
+        // Every token is followed by at least one character of noise, so no number word can span two tokens.
+        let mut line = leading;
+        for (token, separator) in tokens.iter().zip(&separators) {
+            token.push_to(&mut line);
+            line.push_str(separator);
+        }
+
+        prop_assert_eq!(part_2::get_digits(&line), first_and_last(tokens.iter().map(|token| token.value())));
+
+        let digits = tokens.iter().filter_map(|&token| match token {
+            Token::Digit(digit) => Some(digit),
+            Token::Word(_) => None,
+        });
+        prop_assert_eq!(part_1::get_digits(&line), first_and_last(digits));
+    }
+
+    #[test]
+    fn part_2_counts_both_overlapping_words(
+        (overlap, first, last) in select(&OVERLAPS[..]),
+        prefix in OPTIONAL_NOISE,
+        suffix in OPTIONAL_NOISE,
+    ) {
+        let line = format!("{prefix}{overlap}{suffix}");
+
+        prop_assert_eq!(part_2::get_digits(&line), Some((first, last)));
+        prop_assert_eq!(part_1::get_digits(&line), None);
+    }
+}
@@ -0,0 +1,36 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_2::examples::{PART_1, PART_2};
+use day_2::part_1::{are_games_possible, parse_games};
+use day_2::part_2::{is_game_possible, sum_of_powers};
+
+fn bench_day(c: &mut Criterion) {
+    let input = utils::error::read_input(None, utils::bundled_input!()).expect("Failed to read the input!");
+    let inputs = [("example", PART_1.input), ("input", input.as_ref())];
+
+    let mut group = c.benchmark_group("parse");
+    for (name, input) in inputs {
+        group.bench_function(name, |b| b.iter(|| parse_games(black_box(input))));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("part 1");
+    for (name, input) in inputs {
+        let games = parse_games(input).expect("Failed to parse the games!");
+        group.bench_function(name, |b| b.iter(|| are_games_possible(black_box(&games))));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("part 2");
+    for (name, input) in [("example", PART_2.input), inputs[1]] {
+        let games = parse_games(input).expect("Failed to parse the games!");
+        group.bench_function(name, |b| {
+            b.iter(|| sum_of_powers(&black_box(&games).iter().map(is_game_possible).collect::<Vec<_>>()));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_day);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+//! The examples of 2023 day 2 and their answers, from the puzzle page.
+//! Regenerate them with `aoc examples --year 2023 --day 2 --page <page>` rather than editing them.
+
+use utils::example::Example;
+
+/// The example of part 1.
+pub const PART_1: Example = Example {
+    input: "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+    answer: "8",
+};
+
+/// The example of part 2, the same as part 1.
+pub const PART_2: Example = Example { input: PART_1.input, answer: "2286" };
+
+/// The examples of every part, in order.
+pub const EXAMPLES: [Example; 2] = [PART_1, PART_2];
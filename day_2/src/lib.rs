@@ -1,3 +1,5 @@
+pub mod examples;
+
 #[cfg(feature = "part_1")]
 pub mod part_1;
 
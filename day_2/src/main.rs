@@ -1,22 +1,64 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use clap::Parser;
 use color_eyre::eyre::{Result};
+use utils::report::{Format, Report};
+use utils::timing;
 
-fn main() -> Result<()> {
-    let input = include_str!("../input.txt");
+#[cfg(feature = "profile-alloc")]
+#[global_allocator]
+static ALLOCATOR: utils::alloc::CountingAllocator = utils::alloc::CountingAllocator;
 
-    let (games, duration) = utils::time_it(|| {
-        input.lines().map(day_2::part_1::parse_game).collect::<Result<Vec<_>>>()
-    });
-    let games = games?;
-    println!("Part 1: Parsed {length:#?} games in {duration:#?}.", length = games.len());
+#[derive(Debug, Parser)]
+struct Args {
+    /// Read the puzzle input from the given file instead of the bundled `input.txt`, which may be encrypted.
+    #[arg(long, value_name = "PATH")]
+    input: Option<PathBuf>,
 
-    let (id_sum, duration) = utils::time_it(|| day_2::part_1::are_games_possible(&games));
-    println!("Part 1: Found ID sum ({id_sum}) in {duration:#?}.");
+    /// Print a report in the given format (`json`, `csv` or `markdown`) instead of the usual output.
+    #[arg(long)]
+    report: Option<Format>,
 
-    let (bags, duration) = utils::time_it(|| games.iter().map(day_2::part_2::is_game_possible).collect::<Vec<_>>());
-    println!("Part 2: Found {length:#?} bags in {duration:#?}.", length = bags.len());
+    /// Reuse the parsed input from an earlier run on the same input, see `utils::cache`.
+    #[arg(long)]
+    cache: bool,
+}
+
+fn main() -> ExitCode {
+    utils::error::run(solve)
+}
+
+fn solve() -> Result<()> {
+    let args = Args::parse();
+    if args.cache {
+        utils::cache::enable();
+    }
+    let input = utils::error::read_input(args.input.as_deref(), utils::bundled_input!())?;
+    let input = input.as_ref();
+
+    let games = timing::span("parse", || day_2::part_1::parse_games_cached(input))?;
+
+    let id_sum = timing::span("part 1", || day_2::part_1::are_games_possible(&games))?;
+
+    let power = timing::span("part 2", || {
+        let bags = timing::span("bags", || games.iter().map(day_2::part_2::is_game_possible).collect::<Vec<_>>());
+
+        timing::span("power", || day_2::part_2::sum_of_powers(&bags))
+    })?;
+
+    let mut report = Report::new(2023, 2, input, timing::take());
+    report.push(1, id_sum);
+    report.push(2, power);
+
+    if let Some(format) = args.report {
+        print!("{}", report.render(format));
+
+        return Ok(());
+    }
 
-    let (power, duration) = utils::time_it(|| bags.iter().map(day_2::part_2::calculate_power).sum::<u32>());
-    println!("Part 2: Found power ({power}) in {duration:#?}.");
+    println!("Part 1: Parsed {length:#?} games, found ID sum ({id_sum}).", length = games.len());
+    println!("Part 2: Found power ({power}).");
+    print!("\n{}", report.timings);
 
     Ok(())
 }
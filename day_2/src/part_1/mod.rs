@@ -1,5 +1,8 @@
 use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
 use color_eyre::eyre::{Result};
+use utils::error::Error;
+use utils::parse::{self, Input, ParseResult};
 
 /// A game, where each game is a tuple of the game ID and a list of rounds.
 pub type Game = (u32, Vec<HashMap<String, u32>>);
@@ -16,26 +19,108 @@ pub type Game = (u32, Vec<HashMap<String, u32>>);
 ///
 /// # Errors
 ///
-/// * `ParseIntError` - If the game ID cannot be parsed as a `u32`.
+/// * `Error::Parse` - If the game is malformed, with the column at which parsing failed.
 pub fn parse_game(game: &str) -> Result<Game> {
-    let parts = game.split(": ").collect::<Vec<&str>>();
-    let id = parts[0][5..].parse()?;
+    Ok(parse::parse_all(game.trim(), game_parser).map_err(Error::from)?)
+}
+
+/// Given a list of games, one per line, return the game ID and a list of rounds for every game.
+///
+/// # Arguments
+///
+/// * `input` - A string with a game on every line.
+///
+/// # Returns
+///
+/// * `Result<Vec<Game>>` - The games, in the order of the lines.
+///
+/// # Errors
+///
+/// * `Error::Parse` - If a game is malformed, with the line and column at which parsing failed.
+pub fn parse_games(input: &str) -> Result<Vec<Game>> {
+    Ok(parse::parse_all(input, parse::lines(game_parser)).map_err(Error::from)?)
+}
+
+/// Parse every game like [`parse_games`], or load them from the snapshot of an earlier run if the cache is on,
+/// see [`utils::cache`].
+///
+/// # Arguments
+///
+/// * `input` - A string with a game on every line.
+///
+/// # Returns
+///
+/// * `Result<Vec<Game>>` - The games, in the order of the lines.
+///
+/// # Errors
+///
+/// * `Error::Parse` - If the games have to be parsed and one is malformed.
+pub fn parse_games_cached(input: &str) -> Result<Vec<Game>> {
+    utils::cached!(input, parse_games)
+}
+
+/// Given a game, return a value that displays it in the format it is parsed from, so that
+/// [`parse_game`] parses the displayed game back into the same game.
+///
+/// # Arguments
+///
+/// * `game` - A tuple of the game ID and a list of rounds.
+///
+/// # Returns
+///
+/// * `impl Display` - The game, like `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+///
+/// # Examples
+///
+/// ```
+/// use day_2::part_1::{display, parse_game};
+///
+/// let game = parse_game("Game 1: 3 blue; 1 red").unwrap();
+///
+/// assert_eq!(display(&game).to_string(), "Game 1: 3 blue; 1 red");
+/// ```
+#[must_use]
+pub fn display(game: &Game) -> impl Display + '_ {
+    DisplayGame(game)
+}
 
-    let rounds = parts[1].split("; ").map(|round| {
-        let mut round_map = HashMap::new();
+struct DisplayGame<'a>(&'a Game);
 
-        for cube in round.split(", ") {
-            let cube_parts = cube.split(' ').collect::<Vec<&str>>();
-            let count = cube_parts[0].parse()?;
-            let color = cube_parts[1].to_string();
+impl Display for DisplayGame<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (id, rounds) = self.0;
+        write!(f, "Game {id}:")?;
 
-            round_map.insert(color, count);
+        for (index, round) in rounds.iter().enumerate() {
+            let separator = if index == 0 { " " } else { "; " };
+            for (cube, (color, count)) in round.iter().enumerate() {
+                write!(f, "{}{count} {color}", if cube == 0 { separator } else { ", " })?;
+            }
         }
 
-        Ok(round_map)
-    }).collect::<Result<Vec<_>>>()?;
+        Ok(())
+    }
+}
+
+fn game_parser(input: Input<'_>) -> ParseResult<'_, Game> {
+    let cube = |input| {
+        let (count, input) = parse::uint(input)?;
+        let (_, input) = parse::spaces1(input)?;
+        let (color, input) = parse::word(input)?;
+
+        Ok(((color.to_string(), count), input))
+    };
+    let round = |input| {
+        let (cubes, input) = parse::separated(cube, ", ")(input)?;
 
-    Ok((id, rounds))
+        Ok((cubes.into_iter().collect::<HashMap<_, _>>(), input))
+    };
+
+    let (id, input) = parse::keyword_id("Game")(input)?;
+    let (_, input) = parse::spaces(input)?;
+    let (rounds, input) = parse::separated(round, "; ")(input)?;
+
+    Ok(((id, rounds), input))
 }
 
 /// Given a game, return whether or not it is possible.
@@ -70,15 +155,20 @@ pub fn is_game_possible(game: &Game) -> bool {
 ///
 /// # Returns
 ///
-/// * The sum of the IDs of the games that are possible.
-#[must_use]
-pub fn are_games_possible(games: &[Game]) -> u32 {
+/// * `Result<u32>` - The sum of the IDs of the games that are possible.
+///
+/// # Errors
+///
+/// * `Error::Overflow` - If the sum does not fit in a `u32`.
+pub fn are_games_possible(games: &[Game]) -> Result<u32> {
     // Sum up the IDs of the games that are possible.
-    games
+    let sum = games
         .iter()
         .filter(|game| is_game_possible(game))
-        .map(|game| game.0)
-        .sum()
+        .try_fold(0_u32, |sum, game| sum.checked_add(game.0))
+        .ok_or_else(|| Error::Overflow("the sum of the IDs of the games does not fit in a `u32`".to_string()))?;
+
+    Ok(sum)
 }
 
 #[cfg(test)]
@@ -99,16 +189,34 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_game_errors() {
+        let error = |input| parse_game(input).map_err(|error| error.to_string()).err();
+
+        assert_eq!(error("Game: 3 blue"), Some("line 1, column 5: expected a space".to_string()));
+        assert_eq!(error("Game 1: 3 blue, red"), Some("line 1, column 17: `red` is not a number".to_string()));
+        assert_eq!(error("Game 1: 3 blue 4 red"), Some("line 1, column 15: unexpected ` 4 red`".to_string()));
+        assert!(error("Game 1").is_some());
+
+        let games = parse_games("Game 1: 3 blue\nGame 2: 4 green, 1 purple!\n");
+        assert_eq!(
+            games.map_err(|error| error.to_string()).err(),
+            Some("line 2, column 26: unexpected `!`".to_string()),
+        );
+    }
+
+    #[test]
+    fn test_display_game() -> Result<()> {
+        let game = parse_game("Game 12: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")?;
+
+        assert_eq!(parse_game(&display(&game).to_string())?, game);
+
+        Ok(())
+    }
+
     #[test]
     fn test_is_game_possible() -> Result<()> {
-        let inputs = [
-            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
-            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
-            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
-            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
-            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
-        ];
-        let games = inputs.iter().map(|input| parse_game(input)).collect::<Result<Vec<_>>>()?;
+        let games = parse_games(crate::examples::PART_1.input)?;
 
         let expected = vec![true, true, false, false, true];
         let actual = games.iter().map(is_game_possible).collect::<Vec<_>>();
@@ -120,19 +228,16 @@ mod tests {
 
     #[test]
     fn test_are_games_possible() -> Result<()> {
-        let inputs = [
-            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
-            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
-            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
-            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
-            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
-        ];
-        let games = inputs.iter().map(|input| parse_game(input)).collect::<Result<Vec<_>>>()?;
-
-        let expected = 8; // Sum of the IDs of the games that are possible.
-        let actual = are_games_possible(&games);
+        let games = parse_games(crate::examples::PART_1.input)?;
 
-        assert_eq!(expected, actual);
+        // The answer is the sum of the IDs of the games that are possible.
+        let actual = are_games_possible(&games)?;
+
+        assert_eq!(actual.to_string(), crate::examples::PART_1.answer);
+
+        let games = parse_games(&format!("Game {}: 1 red\nGame 1: 1 blue", u32::MAX))?;
+        let error = are_games_possible(&games).err().and_then(|error| error.downcast::<Error>().ok());
+        assert!(matches!(error, Some(Error::Overflow(_))));
 
         Ok(())
     }
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 use std::hash::BuildHasher;
+use color_eyre::eyre::Result;
+use utils::error::Error;
 use crate::part_1::Game;
 
 /// Given a game, return whether or not it is possible.
@@ -39,33 +41,75 @@ pub fn is_game_possible(game: &Game) -> HashMap<String, u32> {
 ///
 /// # Returns
 ///
-/// * `u32` - The power of the bag.
-#[must_use]
-pub fn calculate_power<S: BuildHasher>(bag: &HashMap<String, u32, S>) -> u32 {
-    bag.values().product()
+/// * `Result<u32>` - The power of the bag.
+///
+/// # Errors
+///
+/// * `Error::Overflow` - If the power does not fit in a `u32`.
+pub fn calculate_power<S: BuildHasher>(bag: &HashMap<String, u32, S>) -> Result<u32> {
+    let power = bag
+        .values()
+        .try_fold(1_u32, |power, &count| power.checked_mul(count))
+        .ok_or_else(|| Error::Overflow("the power of a bag does not fit in a `u32`".to_string()))?;
+
+    Ok(power)
+}
+
+/// Given a list of bags, return the sum of their powers.
+///
+/// # Arguments
+///
+/// * `bags` - The bags, see [`is_game_possible`].
+///
+/// # Generics
+///
+/// * `S` - The hasher for the bags.
+///
+/// # Returns
+///
+/// * `Result<u32>` - The sum of the powers of the bags.
+///
+/// # Errors
+///
+/// * `Error::Overflow` - If a power or the sum does not fit in a `u32`.
+pub fn sum_of_powers<S: BuildHasher>(bags: &[HashMap<String, u32, S>]) -> Result<u32> {
+    bags.iter().try_fold(0_u32, |sum, bag| {
+        sum.checked_add(calculate_power(bag)?)
+            .ok_or_else(|| Error::Overflow("the sum of the powers does not fit in a `u32`".to_string()).into())
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use color_eyre::eyre::{Result};
-    use crate::part_1::parse_game;
+    use crate::part_1::parse_games;
 
     use super::*;
 
     #[test]
     fn test_is_game_possible() -> Result<()> {
-        let inputs = [
-            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
-            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
-            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
-            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
-            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
-        ];
-        let games = inputs.iter().map(|input| parse_game(input)).collect::<Result<Vec<_>>>()?;
+        let example = crate::examples::PART_2;
+        let games = parse_games(example.input)?;
         let bags = games.iter().map(is_game_possible).collect::<Vec<_>>();
-        let powers = bags.iter().map(calculate_power).collect::<Vec<_>>();
+        let powers = bags.iter().map(calculate_power).collect::<Result<Vec<_>>>()?;
 
         assert_eq!(powers, [48, 12, 1560, 630, 36]);
+        assert_eq!(sum_of_powers(&bags)?.to_string(), example.answer);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_power_overflow() -> Result<()> {
+        let overflow = |input: &str| -> Result<bool> {
+            let bags = parse_games(input)?.iter().map(is_game_possible).collect::<Vec<_>>();
+            let error = sum_of_powers(&bags).err().and_then(|error| error.downcast::<Error>().ok());
+
+            Ok(matches!(error, Some(Error::Overflow(_))))
+        };
+
+        assert!(overflow("Game 1: 65536 red, 65536 blue")?);
+        assert!(overflow("Game 1: 4294967295 red\nGame 2: 1 red")?);
+        assert!(!overflow("Game 1: 65535 red, 65537 blue")?);
 
         Ok(())
     }
@@ -0,0 +1,34 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_3::examples::{PART_1, PART_2};
+use day_3::Schematic;
+
+fn bench_day(c: &mut Criterion) {
+    let input = utils::error::read_input(None, utils::bundled_input!()).expect("Failed to read the input!");
+    let inputs = [("example", PART_1.input), ("input", input.as_ref())];
+    let parse = |input: &str| input.parse::<Schematic>().expect("Failed to parse the schematic!");
+
+    let mut group = c.benchmark_group("parse");
+    for (name, input) in inputs {
+        group.bench_function(name, |b| b.iter(|| black_box(input).parse::<Schematic>()));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("part 1");
+    for (name, input) in inputs {
+        let schematic = parse(input);
+        group.bench_function(name, |b| b.iter(|| black_box(&schematic).sum_of_parts()));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("part 2");
+    for (name, input) in [("example", PART_2.input), inputs[1]] {
+        let schematic = parse(input);
+        group.bench_function(name, |b| b.iter(|| black_box(&schematic).sum_of_gear_ratios()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_day);
+criterion_main!(benches);
@@ -0,0 +1,4 @@
+fn main() -> std::io::Result<()> {
+    utils::vault::bundle("input.txt")?;
+    utils::cache::fingerprint()
+}
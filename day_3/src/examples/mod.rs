@@ -0,0 +1,26 @@
+//! The examples of 2023 day 3 and their answers, from the puzzle page.
+//! Regenerate them with `aoc examples --year 2023 --day 3 --page <page>` rather than editing them.
+
+use utils::example::Example;
+
+/// The example of part 1.
+pub const PART_1: Example = Example {
+    input: "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..",
+    answer: "4361",
+};
+
+/// The example of part 2, the same as part 1.
+pub const PART_2: Example = Example { input: PART_1.input, answer: "467835" };
+
+/// The examples of every part, in order.
+pub const EXAMPLES: [Example; 2] = [PART_1, PART_2];
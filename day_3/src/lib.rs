@@ -1 +1,6 @@
+mod schematic;
 
+pub mod examples;
+pub mod render;
+
+pub use schematic::{Gear, Number, ParseSchematicError, Schematic, Symbol};
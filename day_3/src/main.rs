@@ -1,53 +1,86 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use clap::Parser;
 use color_eyre::eyre::Result;
+use day_3::Schematic;
+use utils::error::Error;
+use utils::render::Mode;
+use utils::report::{Format, Report};
+use utils::viz::ImageFormat;
+use utils::timing;
 
-fn main() -> Result<()> {
-    let input = r"
-        467..114..
-        ...*......
-        ..35..633.
-        ......#...
-        617*......
-        .....+.58.
-        ..592.....
-        ......755.
-        ...$.*....
-        .664.598..
-    "
-    .trim()
-    .replace([' ', '\n'], "");
-
-    println!("Part 1: {}", sum_of_parts(&input)?);
+#[cfg(feature = "profile-alloc")]
+#[global_allocator]
+static ALLOCATOR: utils::alloc::CountingAllocator = utils::alloc::CountingAllocator;
 
-    Ok(())
+#[derive(Debug, Parser)]
+struct Args {
+    /// Read the puzzle input from the given file instead of the bundled `input.txt`, which may be encrypted.
+    #[arg(long, value_name = "PATH")]
+    input: Option<PathBuf>,
+
+    /// Print a report in the given format (`json`, `csv` or `markdown`) instead of the usual output.
+    #[arg(long)]
+    report: Option<Format>,
+
+    /// Reuse the parsed input from an earlier run on the same input, see `utils::cache`.
+    #[arg(long)]
+    cache: bool,
+
+    /// Print the schematic with part numbers, symbols and gears highlighted, instead of solving it.
+    #[arg(long)]
+    render: bool,
+
+    /// Save the highlighted schematic as an image (`.ppm`, or `.png` with the `png` feature).
+    #[arg(long, value_name = "PATH")]
+    image: Option<PathBuf>,
 }
 
-fn sum_of_parts(input: &str) -> Result<u32> {
-    todo!()
+fn main() -> ExitCode {
+    utils::error::run(solve)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_sum_of_parts() -> Result<()> {
-        let input = r"
-            467..114..
-            ...*......
-            ..35..633.
-            ......#...
-            617*......
-            .....+.58.
-            ..592.....
-            ......755.
-            ...$.*....
-            .664.598..
-        "
-        .trim()
-        .replace([' ', '\n'], "");
-
-        assert_eq!(sum_of_parts(&input)?, 4_361);
-
-        Ok(())
+fn solve() -> Result<()> {
+    let args = Args::parse();
+    if args.cache {
+        utils::cache::enable();
+    }
+    let input = utils::error::read_input(args.input.as_deref(), utils::bundled_input!())?;
+    let input = input.as_ref();
+
+    let schematic = timing::span("parse", || Schematic::parse_cached(input)).map_err(Error::from)?;
+
+    if let Some(path) = args.image {
+        day_3::render::image(&schematic, 4).save(&path, ImageFormat::from_path(&path)?)?;
+        println!("Saved the schematic to {}.", path.display());
+
+        return Ok(());
+    }
+
+    if args.render {
+        let mode = Mode::detect();
+        print!("{}", day_3::render::render(&schematic, mode));
+        println!("\n{}", day_3::render::legend(mode));
+
+        return Ok(());
+    }
+
+    let sum = timing::span("part 1", || schematic.sum_of_parts())?;
+    let ratios = timing::span("part 2", || schematic.sum_of_gear_ratios())?;
+
+    let mut report = Report::new(2023, 3, input, timing::take());
+    report.push(1, sum);
+    report.push(2, ratios);
+
+    if let Some(format) = args.report {
+        print!("{}", report.render(format));
+
+        return Ok(());
     }
+
+    println!("Part 1: {sum}");
+    println!("Part 2: {ratios}");
+    print!("\n{}", report.timings);
+
+    Ok(())
 }
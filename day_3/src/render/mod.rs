@@ -0,0 +1,142 @@
+use utils::grid::Grid;
+use utils::render::{Color, Mode, Style};
+use utils::viz::{Image, Rgb};
+
+use crate::Schematic;
+
+/// The style of the digits of part numbers, which are counted in part 1.
+pub const PART_NUMBER: Style = Style { color: Color::Green, bold: false, marker: '=' };
+/// The style of the digits of numbers that are not adjacent to any symbol.
+pub const UNATTACHED_NUMBER: Style = Style { color: Color::Gray, bold: false, marker: '-' };
+/// The style of symbols that are not gears.
+pub const SYMBOL: Style = Style { color: Color::Yellow, bold: true, marker: '^' };
+/// The style of gears, which are counted in part 2.
+pub const GEAR: Style = Style { color: Color::Magenta, bold: true, marker: '*' };
+
+/// Render the schematic, highlighting part numbers, unattached numbers, symbols and gears.
+///
+/// # Arguments
+///
+/// * `schematic` - The schematic to render.
+/// * `mode` - Whether to highlight with colours or with markers, see [`Mode::detect`].
+///
+/// # Returns
+///
+/// * `String` - The rendered schematic.
+#[must_use]
+pub fn render(schematic: &Schematic, mode: Mode) -> String {
+    let styles = styles(schematic);
+
+    utils::render::render(schematic.grid(), mode, |row, column| styles[(row, column)])
+}
+
+/// Draw the schematic as an image, with the same colours as [`render`] uses on a terminal.
+///
+/// # Arguments
+///
+/// * `schematic` - The schematic to draw.
+/// * `scale` - The width and height of every cell, in pixels.
+///
+/// # Returns
+///
+/// * `Image` - The image, with a square per cell.
+#[must_use]
+pub fn image(schematic: &Schematic, scale: usize) -> Image {
+    const EMPTY: Rgb = [24, 24, 24];
+
+    let styles = styles(schematic);
+
+    Image::from_grid(&styles, scale, |_, _, style| style.map_or(EMPTY, |style| style.color.rgb()))
+}
+
+/// Describe what every colour or marker means.
+///
+/// # Arguments
+///
+/// * `mode` - The mode the schematic was rendered in.
+///
+/// # Returns
+///
+/// * `String` - A line explaining every style.
+#[must_use]
+pub fn legend(mode: Mode) -> String {
+    [(PART_NUMBER, "part number"), (UNATTACHED_NUMBER, "unattached number"), (SYMBOL, "symbol"), (GEAR, "gear")]
+        .iter()
+        .map(|(style, name)| {
+            let sample = Grid::from_fn(1, 1, |_, _| style.marker);
+            let sample = utils::render::render(&sample, mode, |_, _| (mode == Mode::Ansi).then_some(*style));
+
+            format!("{} {name}", sample.trim_end())
+        })
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// Get the style of every cell of the schematic, which [`render`] and [`image`] draw.
+///
+/// # Arguments
+///
+/// * `schematic` - The schematic to style.
+///
+/// # Returns
+///
+/// * `Grid<Option<Style>>` - The style of every cell, or `None` for cells that are not highlighted.
+#[must_use]
+pub fn styles(schematic: &Schematic) -> Grid<Option<Style>> {
+    let mut styles = Grid::from_fn(schematic.width(), schematic.height(), |_, _| None);
+
+    for number in schematic.numbers() {
+        let style = if schematic.is_part_number(number) { PART_NUMBER } else { UNATTACHED_NUMBER };
+        for column in number.start..number.end {
+            styles[(number.row, column)] = Some(style);
+        }
+    }
+    for symbol in schematic.symbols() {
+        styles[(symbol.row, symbol.column)] = Some(SYMBOL);
+    }
+    for gear in schematic.gears() {
+        styles[(gear.symbol.row, gear.symbol.column)] = Some(GEAR);
+    }
+
+    styles
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_render_plain() -> Result<()> {
+        let schematic = "467..114..\n...*......\n..35..633.".parse::<Schematic>()?;
+        let rendered = render(&schematic, Mode::Plain);
+
+        assert_eq!(
+            rendered,
+            "467..114..\n===  ---\n...*......\n   *\n..35..633.\n  ==  ---\n",
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_image() -> Result<()> {
+        let schematic = "1#.".parse::<Schematic>()?;
+        let mut ppm = Vec::new();
+        image(&schematic, 1).write_ppm(&mut ppm)?;
+
+        assert_eq!(ppm[ppm.len() - 9..], [13, 188, 121, 229, 229, 16, 24, 24, 24]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_ansi() -> Result<()> {
+        let schematic = "1#".parse::<Schematic>()?;
+
+        assert_eq!(render(&schematic, Mode::Ansi), "\x1b[32m1\x1b[0m\x1b[1;33m#\x1b[0m\n");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,465 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use utils::error::Error;
+use utils::grid::Grid;
+
+/// A number in the schematic.
+///
+/// # Fields
+///
+/// * `value` - The value of the number.
+/// * `row` - The row the number is on.
+/// * `start` - The column of the first digit.
+/// * `end` - The column after the last digit.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A symbol in the schematic, i.e. anything that is not a digit or a `.`.
+///
+/// # Fields
+///
+/// * `symbol` - The symbol itself.
+/// * `row` - The row the symbol is on.
+/// * `column` - The column the symbol is in.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Symbol {
+    pub symbol: char,
+    pub row: usize,
+    pub column: usize,
+}
+
+impl Number {
+    /// Whether the number touches the given symbol, including diagonally.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - The symbol to check.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the symbol is in one of the cells around the number.
+    #[must_use]
+    pub fn is_adjacent_to(&self, symbol: &Symbol) -> bool {
+        symbol.row + 1 >= self.row
+            && symbol.row <= self.row + 1
+            && symbol.column + 1 >= self.start
+            && symbol.column <= self.end
+    }
+}
+
+/// A `*` symbol that is adjacent to exactly two part numbers.
+///
+/// # Fields
+///
+/// * `symbol` - The `*` symbol.
+/// * `numbers` - The two part numbers.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Gear<'a> {
+    pub symbol: &'a Symbol,
+    pub numbers: [&'a Number; 2],
+}
+
+impl Gear<'_> {
+    /// Get the gear ratio, i.e. the product of the two part numbers.
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The gear ratio, which always fits since both numbers fit in a `u32`.
+    #[must_use]
+    pub fn ratio(&self) -> u64 {
+        u64::from(self.numbers[0].value) * u64::from(self.numbers[1].value)
+    }
+}
+
+/// An engine schematic: a grid of numbers, symbols and `.` cells.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Schematic {
+    grid: Grid<char>,
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    /// The index of the symbol at every position that has one.
+    symbol_positions: HashMap<(usize, usize), usize>,
+}
+
+impl Schematic {
+    /// Parse a schematic like [`FromStr`], or load it from the snapshot of an earlier run if the cache is on,
+    /// see [`utils::cache`].
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The schematic, one row per line.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Schematic, ParseSchematicError>` - The schematic.
+    ///
+    /// # Errors
+    ///
+    /// * `ParseSchematicError` - If the schematic has to be parsed and is malformed.
+    pub fn parse_cached(input: &str) -> Result<Self, ParseSchematicError> {
+        utils::cached!(input, str::parse)
+    }
+
+    /// Get the width of the schematic.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    /// Get the height of the schematic.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    /// Get the cells of the schematic.
+    #[must_use]
+    pub fn grid(&self) -> &Grid<char> {
+        &self.grid
+    }
+
+    /// Get every number in the schematic, in reading order.
+    #[must_use]
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    /// Get every symbol in the schematic, in reading order.
+    #[must_use]
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Get every number adjacent to the given symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - The symbol to find the numbers around.
+    ///
+    /// # Returns
+    ///
+    /// * `impl Iterator<Item = &Number>` - The numbers, in reading order.
+    pub fn numbers_adjacent_to<'a>(&'a self, symbol: &'a Symbol) -> impl Iterator<Item = &'a Number> + 'a {
+        // The numbers are in reading order, so only the ones on the surrounding rows have to be checked.
+        let first = self.numbers.partition_point(|number| number.row + 1 < symbol.row);
+
+        self.numbers[first..]
+            .iter()
+            .take_while(move |number| number.row <= symbol.row + 1)
+            .filter(move |number| number.is_adjacent_to(symbol))
+    }
+
+    /// Get every symbol adjacent to the given number.
+    ///
+    /// # Arguments
+    ///
+    /// * `number` - The number to find the symbols around.
+    ///
+    /// # Returns
+    ///
+    /// * `impl Iterator<Item = &Symbol>` - The symbols, in reading order.
+    pub fn symbols_adjacent_to<'a>(&'a self, number: &Number) -> impl Iterator<Item = &'a Symbol> + 'a {
+        let rows = number.row.saturating_sub(1)..=number.row + 1;
+        let columns = number.start.saturating_sub(1)..=number.end;
+
+        rows.flat_map(move |row| columns.clone().map(move |column| (row, column)))
+            .filter_map(|position| self.symbol_positions.get(&position))
+            .map(|&index| &self.symbols[index])
+    }
+
+    /// Whether the given number is adjacent to any symbol, which makes it a part number.
+    ///
+    /// # Arguments
+    ///
+    /// * `number` - The number to check.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the number is a part number.
+    #[must_use]
+    pub fn is_part_number(&self, number: &Number) -> bool {
+        self.symbols_adjacent_to(number).next().is_some()
+    }
+
+    /// Get every number that is adjacent to at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers.iter().filter(|number| self.is_part_number(number))
+    }
+
+    /// Get every number that is not adjacent to any symbol.
+    pub fn unattached_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers.iter().filter(|number| !self.is_part_number(number))
+    }
+
+    /// Get every `*` symbol that is adjacent to exactly two part numbers.
+    pub fn gears(&self) -> impl Iterator<Item = Gear<'_>> {
+        self.symbols
+            .iter()
+            .filter(|symbol| symbol.symbol == '*')
+            .filter_map(|symbol| {
+                let mut numbers = self.numbers_adjacent_to(symbol);
+                let gear = Gear {
+                    symbol,
+                    numbers: [numbers.next()?, numbers.next()?],
+                };
+
+                numbers.next().is_none().then_some(gear)
+            })
+    }
+
+    /// Get the sum of every part number.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u64, Error>` - The sum of the part numbers.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Overflow` - If the sum does not fit in a `u64`.
+    pub fn sum_of_parts(&self) -> Result<u64, Error> {
+        checked_sum(self.part_numbers().map(|number| u64::from(number.value)), "the sum of the part numbers")
+    }
+
+    /// Get the sum of every gear ratio.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u64, Error>` - The sum of the gear ratios.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Overflow` - If the sum does not fit in a `u64`.
+    pub fn sum_of_gear_ratios(&self) -> Result<u64, Error> {
+        checked_sum(self.gears().map(|gear| gear.ratio()), "the sum of the gear ratios")
+    }
+}
+
+/// Sum some values, failing with an [`Error::Overflow`] naming `what` instead of wrapping around.
+fn checked_sum(mut values: impl Iterator<Item = u64>, what: &str) -> Result<u64, Error> {
+    values.try_fold(0_u64, |sum, value| {
+        sum.checked_add(value).ok_or_else(|| Error::Overflow(format!("{what} does not fit in a `u64`")))
+    })
+}
+
+/// An error that occurred while parsing a schematic.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseSchematicError {
+    /// A row is not as wide as the first row.
+    Ragged { row: usize, width: usize, expected: usize },
+    /// A cell is not ASCII.
+    NonAscii { row: usize, column: usize },
+    /// A number does not fit in a `u32`.
+    Overflow { row: usize, column: usize },
+}
+
+impl Display for ParseSchematicError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ragged { row, width, expected } => {
+                write!(f, "row {} is {width} cells wide, expected {expected}", row + 1)
+            }
+            Self::NonAscii { row, column } => {
+                write!(f, "row {}, column {}: expected an ASCII character", row + 1, column + 1)
+            }
+            Self::Overflow { row, column } => {
+                write!(f, "row {}, column {}: the number is too large", row + 1, column + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseSchematicError {}
+
+impl From<ParseSchematicError> for Error {
+    fn from(error: ParseSchematicError) -> Self {
+        match error {
+            ParseSchematicError::Ragged { row, width, expected } => {
+                Self::parse(row + 1, width.min(expected) + 1, format!("the row is {width} cells wide, expected {expected}"))
+            }
+            ParseSchematicError::NonAscii { row, column } => {
+                Self::parse(row + 1, column + 1, "expected an ASCII character")
+            }
+            ParseSchematicError::Overflow { row, column } => {
+                Self::Overflow(format!("the number at row {}, column {} does not fit in a `u32`", row + 1, column + 1))
+            }
+        }
+    }
+}
+
+impl Display for Schematic {
+    /// Display the schematic one row per line, in the format it is parsed from.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.grid.rows() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Schematic {
+    type Err = ParseSchematicError;
+
+    /// Parse a schematic, one row per line. Surrounding whitespace on every line is ignored.
+    ///
+    /// # Errors
+    ///
+    /// * `ParseSchematicError` - If the rows are not equally wide, a cell is not ASCII or a number is too large.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s.trim().lines().map(str::trim).collect::<Vec<_>>();
+        let width = rows.first().map_or(0, |row| row.len());
+
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+        let mut symbol_positions = HashMap::new();
+
+        for (row, line) in rows.iter().enumerate() {
+            if line.len() != width {
+                return Err(ParseSchematicError::Ragged { row, width: line.len(), expected: width });
+            }
+
+            let mut current: Option<Number> = None;
+            for (column, byte) in line.bytes().enumerate() {
+                if !byte.is_ascii() {
+                    return Err(ParseSchematicError::NonAscii { row, column });
+                }
+
+                if byte.is_ascii_digit() {
+                    let number = current.get_or_insert(Number { value: 0, row, start: column, end: column });
+                    number.value = number
+                        .value
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(u32::from(byte - b'0')))
+                        .ok_or(ParseSchematicError::Overflow { row, column: number.start })?;
+                    number.end = column + 1;
+
+                    continue;
+                }
+
+                numbers.extend(current.take());
+                if byte != b'.' {
+                    symbol_positions.insert((row, column), symbols.len());
+                    symbols.push(Symbol { symbol: char::from(byte), row, column });
+                }
+            }
+            numbers.extend(current);
+        }
+
+        Ok(Self {
+            grid: Grid::from_fn(width, rows.len(), |row, column| char::from(rows[row].as_bytes()[column])),
+            numbers,
+            symbols,
+            symbol_positions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use super::*;
+
+    use crate::examples::{PART_1, PART_2};
+
+    const EXAMPLE: &str = PART_1.input;
+
+    fn values<'a>(numbers: impl Iterator<Item = &'a Number>) -> Vec<u32> {
+        numbers.map(|number| number.value).collect()
+    }
+
+    #[test]
+    fn test_parse_schematic() -> Result<()> {
+        let schematic = EXAMPLE.parse::<Schematic>()?;
+
+        assert_eq!((schematic.width(), schematic.height()), (10, 10));
+        assert_eq!(schematic.numbers().len(), 10);
+        assert_eq!(schematic.numbers()[0], Number { value: 467, row: 0, start: 0, end: 3 });
+        assert_eq!(schematic.symbols().len(), 6);
+        assert_eq!(schematic.symbols()[0], Symbol { symbol: '*', row: 1, column: 3 });
+
+        assert_eq!(
+            "12\n123".parse::<Schematic>(),
+            Err(ParseSchematicError::Ragged { row: 1, width: 3, expected: 2 }),
+        );
+        assert_eq!(
+            "99999999999".parse::<Schematic>(),
+            Err(ParseSchematicError::Overflow { row: 0, column: 0 }),
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_schematic() -> Result<()> {
+        let schematic = EXAMPLE.parse::<Schematic>()?;
+        let displayed = schematic.to_string();
+
+        assert!(displayed.starts_with("467..114..\n...*......\n"));
+        assert_eq!(displayed.parse::<Schematic>()?, schematic);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_adjacency_queries() -> Result<()> {
+        let schematic = EXAMPLE.parse::<Schematic>()?;
+        let symbols = schematic.symbols();
+
+        assert_eq!(values(schematic.numbers_adjacent_to(&symbols[0])), [467, 35]);
+        assert_eq!(values(schematic.numbers_adjacent_to(&symbols[1])), [633]);
+        assert_eq!(values(schematic.numbers_adjacent_to(&symbols[3])), [592]);
+
+        let number = schematic.numbers()[2];
+        let adjacent = schematic.symbols_adjacent_to(&number).map(|symbol| symbol.symbol).collect::<Vec<_>>();
+        assert_eq!(adjacent, ['*']);
+
+        assert_eq!(values(schematic.unattached_numbers()), [114, 58]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gears() -> Result<()> {
+        let schematic = EXAMPLE.parse::<Schematic>()?;
+        let gears = schematic.gears().collect::<Vec<_>>();
+
+        assert_eq!(gears.len(), 2);
+        assert_eq!(gears.iter().map(Gear::ratio).collect::<Vec<_>>(), [16_345, 451_490]);
+        assert_eq!(gears[0].symbol, &Symbol { symbol: '*', row: 1, column: 3 });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sum_of_parts() -> Result<()> {
+        let schematic = EXAMPLE.parse::<Schematic>()?;
+
+        assert_eq!(schematic.sum_of_parts()?.to_string(), PART_1.answer);
+        assert_eq!(schematic.sum_of_gear_ratios()?.to_string(), PART_2.answer);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_large_numbers() -> Result<()> {
+        let schematic = "99999*99999\n...........".parse::<Schematic>()?;
+        assert_eq!(schematic.sum_of_gear_ratios()?, 9_999_800_001);
+
+        let schematic = "4294967295*4294967295".parse::<Schematic>()?;
+        assert_eq!(schematic.sum_of_parts()?, 8_589_934_590);
+        assert_eq!(schematic.sum_of_gear_ratios()?, 18_446_744_065_119_617_025);
+
+        assert!(matches!(checked_sum([u64::MAX, 1].into_iter(), "the sum"), Err(Error::Overflow(_))));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use std::hint::black_box;
+use std::sync::LazyLock;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_4::Card;
+use regex::Regex;
+
+/// The regex based parser that `Card::try_from` used before it was built on `utils::parse`, kept for comparison.
+mod legacy {
+    use super::{LazyLock, Regex};
+
+    static CARD_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"Card\s+(\d+):").expect("Failed to compile regex!"));
+
+    pub fn parse_card(value: &str) -> Option<(usize, Vec<u8>, Vec<u8>)> {
+        let id = CARD_REGEX.captures(value)?.get(1)?.as_str().parse().ok()?;
+        let value = CARD_REGEX.replace(value, "");
+        let mut parts = value.split('|');
+
+        let mut numbers = || {
+            parts
+                .next()?
+                .trim()
+                .split(' ')
+                .filter(|s| !s.is_empty())
+                .map(str::parse)
+                .collect::<Result<Vec<_>, _>>()
+                .ok()
+        };
+        let winning_numbers = numbers()?;
+        let actual_numbers = numbers()?;
+
+        Some((id, winning_numbers, actual_numbers))
+    }
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let input = utils::error::read_input(None, utils::bundled_input!()).expect("Failed to read the input!");
+    let input = input.trim();
+    let mut group = c.benchmark_group("parse");
+
+    group.bench_function("combinator parser", |b| {
+        b.iter(|| {
+            black_box(input)
+                .lines()
+                .map(Card::try_from)
+                .collect::<Result<Vec<_>, _>>()
+        });
+    });
+    group.bench_function("regex parser", |b| {
+        b.iter(|| {
+            black_box(input)
+                .lines()
+                .map(legacy::parse_card)
+                .collect::<Option<Vec<_>>>()
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -0,0 +1,41 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_4::examples::{PART_1, PART_2};
+use day_4::{Card, OverflowPolicy};
+
+fn parse(input: &str) -> Result<Vec<Card>, day_4::ParseCardError> {
+    input.trim().lines().map(Card::try_from).collect()
+}
+
+fn bench_day(c: &mut Criterion) {
+    let input = utils::error::read_input(None, utils::bundled_input!()).expect("Failed to read the input!");
+    let inputs = [("example", PART_1.input), ("input", input.as_ref())];
+
+    let mut group = c.benchmark_group("parse");
+    for (name, input) in inputs {
+        group.bench_function(name, |b| b.iter(|| parse(black_box(input))));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("part 1");
+    for (name, input) in inputs {
+        let cards = parse(input).expect("Failed to parse the cards!");
+        group.bench_function(name, |b| b.iter(|| day_4::sum_points(black_box(&cards))));
+    }
+    group.finish();
+
+    // The recursive count takes a while on the real input, so take fewer samples.
+    let mut group = c.benchmark_group("part 2");
+    group.sample_size(10);
+    for (name, input) in [("example", PART_2.input), inputs[1]] {
+        let cards = parse(input).expect("Failed to parse the cards!");
+        group.bench_function(name, |b| {
+            b.iter(|| day_4::count_copies(black_box(&cards), OverflowPolicy::Error));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_day);
+criterion_main!(benches);
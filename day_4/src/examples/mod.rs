@@ -0,0 +1,22 @@
+//! The examples of 2023 day 4 and their answers, from the puzzle page.
+//! Regenerate them with `aoc examples --year 2023 --day 4 --page <page>` rather than editing them.
+
+use utils::example::Example;
+
+/// The example of part 1.
+pub const PART_1: Example = Example {
+    input: "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+    answer: "13",
+};
+
+/// The example of part 2, the same as part 1.
+pub const PART_2: Example = Example { input: PART_1.input, answer: "30" };
+
+/// The examples of every part, in order.
+pub const EXAMPLES: [Example; 2] = [PART_1, PART_2];
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use utils::grid::Grid;
+use utils::viz::{Image, Rgb};
+
+use crate::Step;
+
+const EMPTY: Rgb = [24, 24, 24];
+const DIM: Rgb = [48, 48, 48];
+const DONE: Rgb = [36, 114, 200];
+const PENDING: Rgb = [118, 118, 118];
+const CURRENT: Rgb = [205, 49, 49];
+const WON: Rgb = [229, 229, 16];
+
+/// Draw the copy cascade as a sequence of frames, one per card.
+///
+/// The cards are laid out in a square, in the order of the table. In every frame the current card is red,
+/// the cards it wins copies of are yellow, the cards that were already processed are blue and the others
+/// are gray, where brighter cards hold more copies.
+///
+/// # Arguments
+///
+/// * `steps` - The steps of the simulation, see [`crate::simulate`].
+/// * `scale` - The width and height of every card, in pixels.
+///
+/// # Returns
+///
+/// * `impl Iterator<Item = Image>` - A frame per step.
+pub fn frames(steps: &[Step], scale: usize) -> impl Iterator<Item = Image> + '_ {
+    let columns = steps.len().isqrt() + usize::from(!is_square(steps.len()));
+    let rows = steps.len().div_ceil(columns.max(1));
+
+    let indices = steps.iter().enumerate().map(|(index, step)| (step.id, index)).collect::<HashMap<_, _>>();
+    let most_copies = steps.iter().map(|step| step.copies).max().unwrap_or(1);
+    let mut copies = vec![1; steps.len()];
+
+    steps.iter().enumerate().map(move |(current, step)| {
+        let won = step.won.iter().filter_map(|id| indices.get(id).copied()).collect::<Vec<_>>();
+        let cells = Grid::from_fn(columns, rows, |row, column| row * columns + column);
+
+        let image = Image::from_grid(&cells, scale, |_, _, &index| {
+            if index >= steps.len() {
+                return EMPTY;
+            }
+
+            let color = if index == current {
+                CURRENT
+            } else if won.contains(&index) {
+                WON
+            } else if index < current {
+                DONE
+            } else {
+                PENDING
+            };
+
+            shade(color, copies[index], most_copies)
+        });
+
+        for &index in &won {
+            copies[index] += step.copies;
+        }
+
+        image
+    })
+}
+
+fn is_square(n: usize) -> bool {
+    n.isqrt() * n.isqrt() == n
+}
+
+/// Blend a colour from dim to full brightness, on a logarithmic scale of the copies.
+fn shade(color: Rgb, copies: usize, most_copies: usize) -> Rgb {
+    let level = copies.max(1).ilog2() + 1;
+    let levels = most_copies.max(1).ilog2() + 1;
+
+    std::array::from_fn(|channel| {
+        let (dim, full) = (u32::from(DIM[channel]), u32::from(color[channel]));
+        let blended = if full >= dim {
+            dim + (full - dim) * level / levels
+        } else {
+            dim - (dim - full) * level / levels
+        };
+
+        u8::try_from(blended).unwrap_or(u8::MAX)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(id: usize, copies: usize, won: &[usize]) -> Step {
+        Step { id, copies, matches: won.len(), won: won.to_vec() }
+    }
+
+    #[test]
+    fn test_frames() -> std::io::Result<()> {
+        let steps = [step(1, 1, &[2, 3]), step(2, 2, &[3]), step(3, 4, &[])];
+        let frames = frames(&steps, 1).collect::<Vec<_>>();
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!((frames[0].width(), frames[0].height()), (2, 2));
+
+        let mut ppm = Vec::new();
+        frames[0].write_ppm(&mut ppm)?;
+        let pixels = ppm[ppm.len() - 12..].chunks(3).collect::<Vec<_>>();
+
+        assert_eq!(pixels[0], shade(CURRENT, 1, 4));
+        assert_eq!(pixels[1], shade(WON, 1, 4));
+        assert_eq!(pixels[2], shade(WON, 1, 4));
+        assert_eq!(pixels[3], EMPTY);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_shade() {
+        assert_eq!(shade(CURRENT, 4, 4), CURRENT);
+        assert_eq!(shade([0, 48, 96], 1, 2), [24, 48, 72]);
+    }
+}
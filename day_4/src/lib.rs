@@ -1,12 +1,27 @@
 use std::collections::HashMap;
-use color_eyre::eyre::{eyre, Result};
-use lazy_static::lazy_static;
-use regex::Regex;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 
-lazy_static! {
-    /// A regex that matches a card.
-    static ref CARD_REGEX: Regex = Regex::new(r"Card\s+(\d+):").expect("Failed to compile regex!");
-}
+use serde::{Deserialize, Serialize};
+use utils::error::Error;
+use utils::timing;
+
+pub mod examples;
+mod parse;
+#[cfg(feature = "part_2")]
+mod simulate;
+#[cfg(feature = "part_2")]
+mod frames;
+#[cfg(feature = "part_2")]
+mod validate;
+
+pub use parse::{ParseCardError, ParseCardErrorKind};
+#[cfg(feature = "part_2")]
+pub use frames::frames;
+#[cfg(feature = "part_2")]
+pub use simulate::{simulate, trace_dot, trace_table, CopiesError, Simulation, Step};
+#[cfg(feature = "part_2")]
+pub use validate::{validate, Diagnostic, DiagnosticKind};
 
 /// A card has winning numbers and the actual numbers present on the card.
 ///
@@ -16,7 +31,7 @@ lazy_static! {
 ///
 /// * `winning_numbers` - The numbers that are required to win the game.
 /// * `actual_numbers` - The numbers that are present on the card.
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[cfg(feature = "part_1")]
 pub struct Card {
     #[cfg(feature = "part_2")]
@@ -31,95 +46,287 @@ impl Card {
     ///
     /// # Returns
     ///
-    /// * `usize` - The points for the card.
+    /// * `Result<usize, Error>` - The points for the card.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::Overflow` - If the card has so many matches that its points do not fit in a `usize`.
+    pub fn points(&self) -> Result<usize, Error> {
+        match self.matches() {
+            0 => Ok(0),
+            // The first match is worth 1 point, and every other match doubles it.
+            matches => u32::try_from(matches - 1)
+                .ok()
+                .and_then(|doublings| 1_usize.checked_shl(doublings))
+                .ok_or_else(|| Error::Overflow(format!("the points of a card with {matches} matches overflow"))),
+        }
+    }
+
+    /// Get the number of winning numbers that are present on the card.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of matches.
     #[must_use]
-    pub fn points(&self) -> usize {
+    pub fn matches(&self) -> usize {
         self.winning_numbers
             .iter()
             .filter(|&n| self.actual_numbers.contains(n))
-            .fold(None, |points, _| {
-                points.map_or(Some(1), |points| Some(points * 2))
-            })
-            .unwrap_or_default()
+            .count()
     }
+
     /// Get the number of cards that we've won.
     ///
     /// # Arguments
     ///
     /// * `card_map` - A map containing all the cards.
+    /// * `last_id` - The last card copies are counted of under the policy, see [`OverflowPolicy::last_id`].
     ///
     /// # Returns
     ///
-    /// * `Option<size>` - The cards that we've won, if any.
+    /// * `Result<usize, CopiesError>` - The cards that we've won, including this one.
+    ///
+    /// # Errors
+    ///
+    /// * `CopiesError::Missing` - If a won card is not in the map, and is not past the last card.
+    /// * `CopiesError::Overflow` - If the number of cards does not fit in a `usize`.
     #[cfg(feature = "part_2")]
-    #[must_use]
-    pub fn calculate_won_cards(&self, card_map: &HashMap<usize, &Self>) -> Option<usize> {
-        let mut found_cards = 1;
-        let wins = self
-            .winning_numbers
-            .iter()
-            .filter(|&n| self.actual_numbers.contains(n))
-            .count();
+    pub fn calculate_won_cards(
+        &self,
+        card_map: &HashMap<usize, &Self>,
+        last_id: LastId,
+    ) -> Result<usize, CopiesError> {
+        let mut found_cards: usize = 1;
+        let wins = self.matches();
         if wins == 0 {
-            return Some(found_cards);
+            return Ok(found_cards);
         }
 
-        for i in 0..wins {
-            let id = self.id + i + 1;
-            let card = card_map.get(&id)?;
+        for i in 1..=wins {
+            let id = self.id.checked_add(i);
+            if last_id.skips(id) {
+                break;
+            }
+
+            let card = id.and_then(|id| card_map.get(&id)).ok_or(MissingCardError {
+                id,
+                won_by: self.id,
+            })?;
 
-            found_cards += card.calculate_won_cards(card_map)?;
+            found_cards = found_cards
+                .checked_add(card.calculate_won_cards(card_map, last_id)?)
+                .ok_or(CopiesError::Overflow { id: self.id })?;
         }
 
-        Some(found_cards)
+        Ok(found_cards)
+    }
+}
+
+/// What to do when a card wins copies of cards past the end of the table.
+///
+/// The puzzle promises that this never happens, so it is an error by default.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum OverflowPolicy {
+    /// Fail with a [`MissingCardError`].
+    #[default]
+    Error,
+    /// Only count the copies of cards up to the last card in the table.
+    Clamp,
+}
+
+impl OverflowPolicy {
+    /// Get the ID of the last card copies are counted of under this policy.
+    ///
+    /// Compute it once per table, since it may look at every card.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The IDs of the cards in the table.
+    ///
+    /// # Returns
+    ///
+    /// * `LastId` - The largest ID for [`OverflowPolicy::Clamp`], or none for [`OverflowPolicy::Error`], since
+    ///   every won card then has to exist.
+    #[must_use]
+    pub fn last_id(self, ids: impl IntoIterator<Item = usize>) -> LastId {
+        match self {
+            Self::Error => LastId(None),
+            Self::Clamp => LastId(Some(ids.into_iter().max().unwrap_or_default())),
+        }
+    }
+}
+
+/// The last card of a table copies are counted of under an [`OverflowPolicy`], see [`OverflowPolicy::last_id`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct LastId(Option<usize>);
+
+impl LastId {
+    /// Whether the copies won of a card are skipped, given its ID, or `None` if it would be past the largest possible
+    /// ID.
+    ///
+    /// Only cards past the last one may be skipped, a gap in the middle of the table is always an error.
+    pub(crate) fn skips(self, id: Option<usize>) -> bool {
+        self.0.is_some_and(|last_id| id.is_none_or(|id| id > last_id))
+    }
+}
+
+impl FromStr for OverflowPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Self::Error),
+            "clamp" => Ok(Self::Clamp),
+            _ => Err(format!("unknown overflow policy `{s}` (expected `error` or `clamp`)")),
+        }
+    }
+}
+
+/// The error returned when a card wins a copy of a card that does not exist.
+///
+/// # Fields
+///
+/// * `id` - The ID of the missing card, or `None` if it would be past the largest possible ID.
+/// * `won_by` - The ID of the card that won a copy of it.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct MissingCardError {
+    pub id: Option<usize>,
+    pub won_by: usize,
+}
+
+impl Display for MissingCardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.id {
+            Some(id) => write!(f, "card {} won a copy of card {id}, which does not exist", self.won_by),
+            None => write!(f, "card {} won a copy of a card past the largest possible ID", self.won_by),
+        }
+    }
+}
+
+impl std::error::Error for MissingCardError {}
+
+impl From<MissingCardError> for Error {
+    fn from(error: MissingCardError) -> Self {
+        Self::Unsolvable(error.to_string())
+    }
+}
+
+/// Parse every card of the input, one per line.
+///
+/// # Arguments
+///
+/// * `input` - The input, with a card on every line.
+///
+/// # Returns
+///
+/// * `Result<Vec<Card>, Error>` - The cards.
+///
+/// # Errors
+///
+/// * `Error::Parse` - If a line is not a card, with the line and column of the error.
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, Error> {
+    // Blank lines are skipped rather than trimmed away, so the lines of the errors match the input.
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| Card::try_from(line).map_err(|error| Error::parse(index + 1, error.column, error.kind)))
+        .collect()
+}
+
+/// Add up the points of every card.
+///
+/// # Arguments
+///
+/// * `cards` - The cards.
+///
+/// # Returns
+///
+/// * `Result<usize, Error>` - The points.
+///
+/// # Errors
+///
+/// * `Error::Overflow` - If the points of a card or their sum do not fit in a `usize`.
+pub fn sum_points(cards: &[Card]) -> Result<usize, Error> {
+    cards.iter().try_fold(0_usize, |total, c| {
+        total
+            .checked_add(c.points()?)
+            .ok_or_else(|| Error::Overflow("the number of points does not fit in a `usize`".to_string()))
+    })
+}
+
+/// Parse every card like [`parse_cards`], or load them from the snapshot of an earlier run if the cache is on,
+/// see [`utils::cache`].
+///
+/// # Arguments
+///
+/// * `input` - The input, with a card on every line.
+///
+/// # Returns
+///
+/// * `Result<Vec<Card>, Error>` - The cards.
+///
+/// # Errors
+///
+/// * `Error::Parse` - If the cards have to be parsed and a line is not a card.
+pub fn parse_cards_cached(input: &str) -> Result<Vec<Card>, Error> {
+    utils::cached!(input, parse_cards)
+}
+
+/// Count every card we end up with, originals and copies.
+///
+/// # Arguments
+///
+/// * `cards` - The cards.
+/// * `policy` - What to do when a card wins copies of cards past the end of the table.
+///
+/// # Returns
+///
+/// * `Result<usize, Error>` - The number of cards.
+///
+/// # Errors
+///
+/// * `Error::Unsolvable` - If a card wins a copy of a card that does not exist, and the policy does not allow it.
+/// * `Error::Overflow` - If the number of cards does not fit in a `usize`.
+#[cfg(feature = "part_2")]
+pub fn count_copies(cards: &[Card], policy: OverflowPolicy) -> Result<usize, Error> {
+    let card_map = timing::span("card map", || cards.iter().map(|c| (c.id, c)).collect::<HashMap<_, _>>());
+    let last_id = policy.last_id(card_map.keys().copied());
+
+    timing::span("copies", || {
+        cards.iter().try_fold(0_usize, |total, c| {
+            total
+                .checked_add(c.calculate_won_cards(&card_map, last_id)?)
+                .ok_or_else(|| Error::Overflow("the number of copies does not fit in a `usize`".to_string()))
+        })
+    })
+}
+
+impl Display for Card {
+    /// Display the card in the format it is parsed from, e.g. `Card 1: 41 48 | 83 86 6`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let join = |numbers: &[u8]| numbers.iter().map(ToString::to_string).collect::<Vec<_>>().join(" ");
+
+        // Without part 2 the ID is not kept, so any ID parses back into the same card.
+        #[cfg(feature = "part_2")]
+        let id = self.id;
+        #[cfg(not(feature = "part_2"))]
+        let id = 1;
+
+        write!(f, "Card {id}: {} | {}", join(&self.winning_numbers), join(&self.actual_numbers))
     }
 }
 
 impl TryFrom<&str> for Card {
-    type Error = color_eyre::eyre::Error;
+    type Error = ParseCardError;
 
+    /// Parse a card from a line like `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`.
+    ///
+    /// # Errors
+    ///
+    /// * `ParseCardError` - If the line is malformed, with the column and the kind of the error.
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        /*
-        A string should look like this: "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
-        We need to split the string into two parts: the winning numbers and the actual numbers.
-        The winning numbers are the numbers before the pipe character, and the actual numbers
-        are the numbers after the pipe character.
-        */
-
-        // Extract the "Card X: " (ID) part of the string.
-        let id = CARD_REGEX
-            .captures(value)
-            .ok_or_else(|| eyre!("No card ID found!"))?
-            .get(1)
-            .ok_or_else(|| eyre!("No card ID found!"))?
-            .as_str()
-            .parse()?;
-
-        // Remove the "Card X: " (ID) part of the string.
-        let value = CARD_REGEX.replace(value, "");
-
-        // Split the string into two parts, using the pipe character as the separator.
-        let mut parts = value.split('|');
-
-        // The winning numbers are the first part of the string.
-        let winning_numbers = parts
-            .next()
-            .ok_or_else(|| eyre!("No winning numbers found!"))?
-            .trim()
-            .split(' ')
-            .filter(|s| !s.is_empty())
-            .map(str::parse)
-            .collect::<Result<Vec<_>, _>>()?;
-
-        // The actual numbers are the second part of the string.
-        let actual_numbers = parts
-            .next()
-            .ok_or_else(|| eyre!("No actual numbers found!"))?
-            .trim()
-            .split(' ')
-            .filter(|s| !s.is_empty())
-            .map(str::parse)
-            .collect::<Result<Vec<_>, _>>()?;
+        let (id, winning_numbers, actual_numbers) = parse::parse_card(value)?;
 
         Ok(Self {
             id,
@@ -132,8 +339,17 @@ impl TryFrom<&str> for Card {
 
 #[cfg(test)]
 mod tests {
+    use color_eyre::eyre::Result;
+
     use super::*;
 
+    #[test]
+    fn test_parse_cards_lines() {
+        let error = parse_cards("\n\nCard 1: 1 | 2\n\nCard 2: 1 | x\n").map_err(|error| error.to_string()).err();
+
+        assert_eq!(error, Some("line 5, column 13: `x` is not a number".to_string()));
+    }
+
     #[test]
     fn test_parse_card() -> Result<()> {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
@@ -142,30 +358,30 @@ mod tests {
         assert_eq!(card.winning_numbers, vec![41, 48, 83, 86, 17]);
         assert_eq!(card.actual_numbers, vec![83, 86, 6, 31, 17, 9, 48, 53]);
 
+        assert_eq!(card.to_string(), "Card 1: 41 48 83 86 17 | 83 86 6 31 17 9 48 53");
+        assert_eq!(Card::try_from(card.to_string().as_str())?, card);
+
         Ok(())
     }
 
     #[test]
     fn test_get_points() -> Result<()> {
-        let input = r"
-            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-            Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-            Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-            Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-            Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
-        "
-        .trim();
+        let input = examples::PART_1.input;
 
         let cards = input
             .lines()
             .map(Card::try_from)
-            .collect::<Result<Vec<_>>>()?;
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let actual_points = cards.iter().map(Card::points).collect::<Vec<_>>();
+        let actual_points = cards.iter().map(Card::points).collect::<Result<Vec<_>, _>>()?;
         let expected_points = vec![8, 2, 2, 1, 0, 0];
 
         assert_eq!(actual_points, expected_points);
+        assert_eq!(sum_points(&cards)?.to_string(), examples::PART_1.answer);
+
+        let numbers = (0..=64).map(|number| number.to_string()).collect::<Vec<_>>().join(" ");
+        let card = Card::try_from(format!("Card 1: {numbers} | {numbers}").as_str())?;
+        assert!(matches!(card.points(), Err(Error::Overflow(_))));
 
         Ok(())
     }
@@ -173,29 +389,101 @@ mod tests {
     #[cfg(feature = "part_2")]
     #[test]
     fn test_calculate_won_cards() -> Result<()> {
+        let example = examples::PART_2;
+
+        let cards = example
+            .input
+            .lines()
+            .map(Card::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let card_map = cards.iter().map(|c| (c.id, c)).collect::<HashMap<_, _>>();
+
+        let actual_won_cards = cards
+            .iter()
+            .map(|c| c.calculate_won_cards(&card_map, OverflowPolicy::Error.last_id([])))
+            .sum::<Result<usize, _>>()?;
+
+        assert_eq!(actual_won_cards.to_string(), example.answer);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "part_2")]
+    #[test]
+    fn test_overflow_policy() -> Result<()> {
         let input = r"
             Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
             Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-            Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-            Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-            Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-            Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
         "
         .trim();
 
         let cards = input
             .lines()
             .map(Card::try_from)
-            .collect::<Result<Vec<_>>>()?;
+            .collect::<Result<Vec<_>, _>>()?;
         let card_map = cards.iter().map(|c| (c.id, c)).collect::<HashMap<_, _>>();
 
-        let actual_won_cards = cards
+        let last_id = OverflowPolicy::Clamp.last_id(card_map.keys().copied());
+
+        assert_eq!(
+            cards[0].calculate_won_cards(&card_map, OverflowPolicy::Error.last_id(card_map.keys().copied())),
+            Err(CopiesError::Missing(MissingCardError { id: Some(3), won_by: 2 })),
+        );
+        assert_eq!(cards[0].calculate_won_cards(&card_map, last_id), Ok(2));
+        assert_eq!(cards[1].calculate_won_cards(&card_map, last_id), Ok(1));
+
+        Ok(())
+    }
+
+    /// Inputs the `day_4_cards` fuzz target crashed on, see `fuzz/`, on a 64-bit target where the ID was
+    /// `18446744073709551615`.
+    #[cfg(feature = "part_2")]
+    fn fuzz_crashes() -> [String; 2] {
+        let max = usize::MAX;
+
+        [format!("\u{1f} \nCard {max}:5|5\n "), format!("\nCard {max}:0 |0")]
+    }
+
+    #[cfg(feature = "part_2")]
+    #[test]
+    fn test_fuzz_crashes() -> Result<()> {
+        let wins_past_end = DiagnosticKind::WinsPastEnd { id: usize::MAX, matches: 1, last_id: usize::MAX };
+
+        let crashes = fuzz_crashes();
+        for input in &crashes {
+            let diagnostics = validate(input);
+            assert!(diagnostics.iter().any(|diagnostic| diagnostic.line == 2 && diagnostic.kind == wins_past_end));
+        }
+
+        let cards = parse_cards(&crashes[1])?;
+        assert_eq!(sum_points(&cards)?, 1);
+        assert!(matches!(count_copies(&cards, OverflowPolicy::Error), Err(Error::Unsolvable(_))));
+        assert_eq!(count_copies(&cards, OverflowPolicy::Clamp)?, 1);
+
+        let steps = simulate(&cards, OverflowPolicy::Error).collect::<Vec<_>>();
+        assert_eq!(steps, [Err(CopiesError::Missing(MissingCardError { id: None, won_by: usize::MAX }))]);
+        let steps = simulate(&cards, OverflowPolicy::Clamp).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(steps.iter().map(|step| step.copies).collect::<Vec<_>>(), [1]);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "part_2")]
+    #[test]
+    fn test_large_ids() -> Result<()> {
+        let cards = [format!("Card {}: 1 2 | 1 2", usize::MAX - 1), format!("Card {}: 1 | 1", usize::MAX)]
             .iter()
-            .map(|c| c.calculate_won_cards(&card_map).unwrap_or_default())
-            .sum::<usize>();
-        let expected_won_cards = 30;
+            .map(|line| Card::try_from(line.as_str()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let card_map = cards.iter().map(|c| (c.id, c)).collect::<HashMap<_, _>>();
 
-        assert_eq!(actual_won_cards, expected_won_cards);
+        assert_eq!(
+            cards[1].calculate_won_cards(&card_map, OverflowPolicy::Error.last_id(card_map.keys().copied())),
+            Err(CopiesError::Missing(MissingCardError { id: None, won_by: usize::MAX })),
+        );
+        let last_id = OverflowPolicy::Clamp.last_id(card_map.keys().copied());
+        assert_eq!(cards[0].calculate_won_cards(&card_map, last_id), Ok(2));
+        assert_eq!(count_copies(&cards, OverflowPolicy::Clamp)?, 3);
 
         Ok(())
     }
@@ -1,34 +1,146 @@
-use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+use clap::Parser;
 use color_eyre::eyre::Result;
-use day_4::Card;
-
-fn main() -> Result<()> {
-    let input = include_str!("../input.txt");
-    let (cards, duration) = utils::time_it(|| {
-        input
-            .trim()
-            .lines()
-            .map(Card::try_from)
-            .collect::<Result<Vec<_>, _>>()
-    });
-    let cards = cards?;
-    println!("Parsing took {duration:#?}.");
-
-    let (points, duration) = utils::time_it(|| cards.iter().map(Card::points).sum::<usize>());
-    println!("Calculating points took {duration:#?}.");
+use day_4::{DiagnosticKind, OverflowPolicy};
+use utils::error::Error;
+use utils::report::{Format, Report};
+use utils::timing;
+use utils::viz::{Frames, ImageFormat};
 
-    println!("Part 1: {points} points.");
+#[cfg(feature = "profile-alloc")]
+#[global_allocator]
+static ALLOCATOR: utils::alloc::CountingAllocator = utils::alloc::CountingAllocator;
+
+#[derive(Debug, Parser)]
+struct Args {
+    /// Read the puzzle input from the given file instead of the bundled `input.txt`, which may be encrypted.
+    #[arg(long, value_name = "PATH")]
+    input: Option<PathBuf>,
+
+    /// Print a report in the given format (`json`, `csv` or `markdown`) instead of the usual output.
+    #[arg(long)]
+    report: Option<Format>,
+
+    /// Reuse the parsed input from an earlier run on the same input, see `utils::cache`.
+    #[arg(long)]
+    cache: bool,
+
+    /// Check the input for anomalies and report all of them, instead of solving it.
+    #[arg(long)]
+    validate: bool,
+
+    /// What to do when a card wins copies of cards past the end of the table (`error` or `clamp`).
+    #[arg(long, default_value = "error")]
+    overflow: OverflowPolicy,
+
+    /// Print how the copies cascade through the cards, as a `table` or a Graphviz `dot` graph.
+    #[arg(long, num_args = 0..=1, default_missing_value = "table")]
+    trace: Option<TraceFormat>,
+
+    /// Write the copy cascade as a sequence of images, one per card, to the given directory.
+    #[arg(long, value_name = "DIRECTORY")]
+    frames: Option<PathBuf>,
+
+    /// The format of the frames (`ppm`, or `png` with the `png` feature).
+    #[arg(long, default_value = "ppm")]
+    frame_format: ImageFormat,
+}
+
+/// The formats the copy cascade can be traced in.
+#[derive(Debug, Clone, Copy)]
+enum TraceFormat {
+    Table,
+    Dot,
+}
+
+impl FromStr for TraceFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Self::Table),
+            "dot" => Ok(Self::Dot),
+            _ => Err(format!("unknown trace format `{s}` (expected `table` or `dot`)")),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    utils::error::run(solve)
+}
 
-    let card_map = cards.iter().map(|c| (c.id, c)).collect::<HashMap<_, _>>();
-    let (copies, duration) = utils::time_it(|| {
-        cards
-            .iter()
-            .map(|c| c.calculate_won_cards(&card_map).unwrap_or_default())
-            .sum::<usize>()
-    });
-    println!("Calculating copies took {duration:#?}.");
+fn solve() -> Result<()> {
+    let args = Args::parse();
+    if args.cache {
+        utils::cache::enable();
+    }
+    let input = utils::error::read_input(args.input.as_deref(), utils::bundled_input!())?;
+    let input = input.as_ref();
 
+    if args.validate {
+        let diagnostics = day_4::validate(input);
+        for diagnostic in &diagnostics {
+            println!("{diagnostic}");
+        }
+
+        // Anomalies are malformed input, so they fail like a parse error, at the first one.
+        if let Some(first) = diagnostics.first() {
+            let column = match &first.kind {
+                DiagnosticKind::Parse(error) => error.column,
+                _ => 1,
+            };
+
+            return Err(Error::parse(first.line, column, format!("found {} anomalies", diagnostics.len())).into());
+        }
+        println!("No anomalies found.");
+
+        return Ok(());
+    }
+
+    let cards = timing::span("parse", || day_4::parse_cards_cached(input))?;
+
+    if let Some(directory) = args.frames {
+        let steps = day_4::simulate(&cards, args.overflow).collect::<Result<Vec<_>, _>>().map_err(Error::from)?;
+
+        let mut frames = Frames::create(&directory, args.frame_format)?;
+        for frame in day_4::frames(&steps, 8) {
+            frames.push(&frame)?;
+        }
+        println!("Wrote {} frames to {}.", frames.len(), directory.display());
+
+        return Ok(());
+    }
+
+    if let Some(format) = args.trace {
+        let steps = day_4::simulate(&cards, args.overflow).collect::<Result<Vec<_>, _>>().map_err(Error::from)?;
+
+        match format {
+            TraceFormat::Table => print!("{}", day_4::trace_table(&steps)),
+            TraceFormat::Dot => print!("{}", day_4::trace_dot(&steps)),
+        }
+
+        return Ok(());
+    }
+
+    let points = timing::span("part 1", || day_4::sum_points(&cards))?;
+
+    let copies = timing::span("part 2", || day_4::count_copies(&cards, args.overflow))?;
+
+    let mut report = Report::new(2023, 4, input, timing::take());
+    report.push(1, points);
+    report.push(2, copies);
+
+    if let Some(format) = args.report {
+        print!("{}", report.render(format));
+
+        return Ok(());
+    }
+
+    println!("Part 1: {points} points.");
     println!("Part 2: {copies} copies.");
+    print!("\n{}", report.timings);
 
     Ok(())
 }
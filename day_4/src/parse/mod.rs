@@ -0,0 +1,178 @@
+use std::fmt::{self, Display, Formatter};
+
+use utils::parse::{self, ErrorKind, Input, ParseError};
+
+/// An error that occurred while parsing a card.
+///
+/// # Fields
+///
+/// * `column` - The column (starting at 1) at which the error occurred.
+/// * `kind` - What went wrong.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseCardError {
+    pub column: usize,
+    pub kind: ParseCardErrorKind,
+}
+
+/// The kinds of errors that can occur while parsing a card.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseCardErrorKind {
+    /// The line does not start with `Card`.
+    MissingPrefix,
+    /// There is no card ID after `Card`.
+    MissingId,
+    /// There is no `:` after the card ID.
+    MissingColon,
+    /// There is no `|` between the winning and the actual numbers.
+    MissingSeparator,
+    /// A token is not a number.
+    NonNumeric(String),
+    /// A number does not fit in its type, i.e. `u8` for numbers and `usize` for the card ID.
+    OutOfRange(String),
+    /// A number appears twice in the same list.
+    DuplicateNumber(u8),
+}
+
+impl Display for ParseCardErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPrefix => write!(f, "expected `Card`"),
+            Self::MissingId => write!(f, "expected a card ID"),
+            Self::MissingColon => write!(f, "expected `:` after the card ID"),
+            Self::MissingSeparator => write!(f, "expected `|` after the winning numbers"),
+            Self::NonNumeric(token) => write!(f, "`{token}` is not a number"),
+            Self::OutOfRange(token) => write!(f, "`{token}` is out of range"),
+            Self::DuplicateNumber(number) => write!(f, "{number} appears more than once"),
+        }
+    }
+}
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.kind)
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+/// The parts of a card, as they appear on a line.
+pub(crate) type RawCard = (usize, Vec<u8>, Vec<u8>);
+
+/// Parse a line like `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`.
+///
+/// Leading and trailing spaces are ignored.
+///
+/// # Arguments
+///
+/// * `line` - The line to parse.
+///
+/// # Returns
+///
+/// * `Result<RawCard, ParseCardError>` - The ID, the winning numbers and the actual numbers.
+///
+/// # Errors
+///
+/// * `ParseCardError` - If the line does not match the grammar, see [`ParseCardErrorKind`].
+pub(crate) fn parse_card(line: &str) -> Result<RawCard, ParseCardError> {
+    let input = Input::new(line).advance(line.len() - line.trim_start_matches(' ').len());
+    let (_, input) = parse::tag("Card")(input).map_err(|error| convert(error, ParseCardErrorKind::MissingPrefix))?;
+    let (_, input) = parse::spaces1(input).map_err(|error| convert(error, ParseCardErrorKind::MissingId))?;
+    let (id, input) = parse::uint(input).map_err(|error| convert(error, ParseCardErrorKind::MissingId))?;
+    let (_, input) = parse::tag(":")(input).map_err(|error| convert(error, ParseCardErrorKind::MissingColon))?;
+
+    let (winning_numbers, input) = numbers(input)?;
+    let (_, input) = parse::tag("|")(input).map_err(|error| {
+        if input.is_empty() {
+            convert(error, ParseCardErrorKind::MissingSeparator)
+        } else {
+            unexpected(input)
+        }
+    })?;
+
+    let (actual_numbers, input) = numbers(input)?;
+    if !input.is_empty() {
+        return Err(unexpected(input));
+    }
+
+    Ok((id, winning_numbers, actual_numbers))
+}
+
+/// Parse a list of space separated numbers, which ends at the end of the line or at punctuation like `|`.
+fn numbers(input: Input<'_>) -> Result<(Vec<u8>, Input<'_>), ParseCardError> {
+    let (numbers, rest) = parse::whitespace_list(parse::uint::<u8>)(input)
+        .map_err(|error| convert(error, ParseCardErrorKind::MissingSeparator))?;
+
+    let mut seen = [false; 256];
+    if let Some(duplicate) = numbers.iter().position(|&number| std::mem::replace(&mut seen[usize::from(number)], true)) {
+        // Only look for where the duplicate is when there is one, to keep the common case fast.
+        let mut position = input;
+        for _ in 0..duplicate {
+            let Ok((_, rest)) = parse::spaces(position).and_then(|(_, rest)| parse::uint::<u8>(rest)) else {
+                break;
+            };
+            position = rest;
+        }
+        let (_, position) = parse::spaces(position).map_err(|error| convert(error, ParseCardErrorKind::MissingSeparator))?;
+
+        return Err(ParseCardError {
+            column: position.location().1,
+            kind: ParseCardErrorKind::DuplicateNumber(numbers[duplicate]),
+        });
+    }
+
+    Ok((numbers, rest))
+}
+
+/// Convert an error of a [`parse`] primitive, using `missing` for when something was expected but not found.
+fn convert(error: ParseError, missing: ParseCardErrorKind) -> ParseCardError {
+    let kind = match error.kind {
+        ErrorKind::NotANumber(token) => ParseCardErrorKind::NonNumeric(token),
+        ErrorKind::OutOfRange(token) => ParseCardErrorKind::OutOfRange(token),
+        ErrorKind::Expected(_) | ErrorKind::Trailing(_) => missing,
+    };
+
+    ParseCardError {
+        column: error.column,
+        kind,
+    }
+}
+
+/// Report the token at the rest of the input as not being a number, e.g. a second `|`.
+fn unexpected(input: Input<'_>) -> ParseCardError {
+    let token = input.rest().split_whitespace().next().unwrap_or_default();
+
+    ParseCardError {
+        column: input.location().1,
+        kind: ParseCardErrorKind::NonNumeric(token.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(line: &str) -> Option<(usize, ParseCardErrorKind)> {
+        parse_card(line).err().map(|error| (error.column, error.kind))
+    }
+
+    #[test]
+    fn test_parse_card() {
+        let card = parse_card("Card   3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1");
+
+        assert_eq!(card, Ok((3, vec![1, 21, 53, 59, 44], vec![69, 82, 63, 72, 16, 21, 14, 1])));
+        assert_eq!(parse_card("  Card 7: | 1 "), Ok((7, vec![], vec![1])));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(kind("Crad 1: 1 | 2"), Some((1, ParseCardErrorKind::MissingPrefix)));
+        assert_eq!(kind("Card: 1 | 2"), Some((5, ParseCardErrorKind::MissingId)));
+        assert_eq!(kind("Card 1 1 | 2"), Some((7, ParseCardErrorKind::MissingColon)));
+        assert_eq!(kind("Card 1: 1 2 3"), Some((14, ParseCardErrorKind::MissingSeparator)));
+        assert_eq!(kind("Card 1: 1 x2 | 3"), Some((11, ParseCardErrorKind::NonNumeric("x2".to_string()))));
+        assert_eq!(kind("Card 1: 1 | 3 | 4"), Some((15, ParseCardErrorKind::NonNumeric("|".to_string()))));
+        assert_eq!(kind("Card 1: 256 | 3"), Some((9, ParseCardErrorKind::OutOfRange("256".to_string()))));
+        assert_eq!(kind("Card 1: 4 5 4 | 3"), Some((13, ParseCardErrorKind::DuplicateNumber(4))));
+        assert_eq!(kind("Card 1: 4 | 3 3"), Some((15, ParseCardErrorKind::DuplicateNumber(3))));
+    }
+}
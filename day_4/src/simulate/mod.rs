@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter, Write};
+
+use utils::error::Error;
+
+use crate::{Card, LastId, MissingCardError, OverflowPolicy};
+
+/// What happened to a single card while the copies cascaded through the table.
+///
+/// # Fields
+///
+/// * `id` - The ID of the card.
+/// * `copies` - The number of copies of the card that were held, including the original.
+/// * `matches` - The number of winning numbers present on the card.
+/// * `won` - The IDs of the later cards that received a copy for every copy of this card.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Step {
+    pub id: usize,
+    pub copies: usize,
+    pub matches: usize,
+    pub won: Vec<usize>,
+}
+
+/// The error returned when the copies won by a table of cards cannot be counted.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CopiesError {
+    /// A card won a copy of a card that does not exist.
+    Missing(MissingCardError),
+    /// The table is not sorted by ID, so a card could win copies of a card that was already simulated. Only the
+    /// simulation needs a sorted table.
+    Unsorted { id: usize, previous: usize },
+    /// The number of copies of the card does not fit in a `usize`.
+    Overflow { id: usize },
+}
+
+impl Display for CopiesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(error) => Display::fmt(error, f),
+            Self::Unsorted { id, previous } => write!(f, "card {id} comes after card {previous}, sort the cards by ID"),
+            Self::Overflow { id } => write!(f, "the number of copies of card {id} does not fit in a `usize`"),
+        }
+    }
+}
+
+impl std::error::Error for CopiesError {}
+
+impl From<MissingCardError> for CopiesError {
+    fn from(error: MissingCardError) -> Self {
+        Self::Missing(error)
+    }
+}
+
+impl From<CopiesError> for Error {
+    fn from(error: CopiesError) -> Self {
+        match error {
+            CopiesError::Missing(error) => error.into(),
+            CopiesError::Unsorted { .. } => Self::Unsolvable(error.to_string()),
+            CopiesError::Overflow { .. } => Self::Overflow(error.to_string()),
+        }
+    }
+}
+
+/// A step-by-step simulation of the copies won in part 2.
+///
+/// Unlike [`Card::calculate_won_cards`], every card is only visited once: each copy of a card wins one
+/// copy of each of the next cards, so the copies of a card are final by the time it is reached.
+#[derive(Debug, Clone)]
+pub struct Simulation<'a> {
+    cards: &'a [Card],
+    indices: HashMap<usize, usize>,
+    copies: Vec<usize>,
+    last_id: LastId,
+    next: usize,
+    unsorted: Option<CopiesError>,
+}
+
+/// Simulate the copies won by a table of cards.
+///
+/// # Arguments
+///
+/// * `cards` - The cards, in the order of the table, which has to be sorted by ID.
+/// * `policy` - What to do when a card wins copies of cards past the end of the table.
+///
+/// # Returns
+///
+/// * `Simulation` - An iterator over the step of every card, in the order of the table, or a single error if the
+///   table is not sorted by ID.
+///
+/// # Examples
+///
+/// ```
+/// use day_4::{simulate, Card, OverflowPolicy};
+///
+/// let cards = ["Card 1: 1 2 | 1 2", "Card 2: 3 | 3", "Card 3: 4 | 5"]
+///     .into_iter()
+///     .map(Card::try_from)
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// let steps = simulate(&cards, OverflowPolicy::Error).collect::<Result<Vec<_>, _>>().unwrap();
+///
+/// assert_eq!(steps.iter().map(|step| step.copies).collect::<Vec<_>>(), [1, 2, 4]);
+/// assert_eq!(steps[0].won, [2, 3]);
+/// ```
+#[must_use]
+pub fn simulate(cards: &[Card], policy: OverflowPolicy) -> Simulation<'_> {
+    // Every card only wins copies of later cards, so the copies of a card are only final if no later card comes
+    // before it.
+    let unsorted = cards
+        .windows(2)
+        .find(|pair| pair[1].id <= pair[0].id)
+        .map(|pair| CopiesError::Unsorted { id: pair[1].id, previous: pair[0].id });
+
+    Simulation {
+        cards,
+        indices: cards.iter().enumerate().map(|(index, card)| (card.id, index)).collect(),
+        copies: vec![1; cards.len()],
+        last_id: policy.last_id(cards.iter().map(|card| card.id)),
+        next: 0,
+        unsorted,
+    }
+}
+
+impl Simulation<'_> {
+    /// Give a copy of a card, whose ID is `None` if it would be past the largest possible ID, to every copy of the
+    /// card that won it.
+    fn win(&mut self, won_by: usize, id: Option<usize>, copies: usize) -> Result<usize, CopiesError> {
+        let (id, index) = id
+            .and_then(|id| Some((id, *self.indices.get(&id)?)))
+            .ok_or(MissingCardError { id, won_by })?;
+        self.copies[index] = self.copies[index].checked_add(copies).ok_or(CopiesError::Overflow { id })?;
+
+        Ok(id)
+    }
+}
+
+impl Iterator for Simulation<'_> {
+    type Item = Result<Step, CopiesError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.unsorted.take() {
+            self.next = self.cards.len();
+
+            return Some(Err(error));
+        }
+
+        let card = self.cards.get(self.next)?;
+        let copies = self.copies[self.next];
+        let matches = card.matches();
+        self.next += 1;
+
+        let mut won = Vec::with_capacity(matches);
+        for i in 1..=matches {
+            let id = card.id.checked_add(i);
+            if self.last_id.skips(id) {
+                break;
+            }
+
+            match self.win(card.id, id, copies) {
+                Ok(id) => won.push(id),
+                Err(error) => {
+                    // Stop the simulation, since the copies of the later cards can no longer be trusted.
+                    self.next = self.cards.len();
+
+                    return Some(Err(error));
+                }
+            }
+        }
+
+        Some(Ok(Step {
+            id: card.id,
+            copies,
+            matches,
+            won,
+        }))
+    }
+}
+
+/// Render the steps of a simulation as a table.
+///
+/// # Arguments
+///
+/// * `steps` - The steps of the simulation.
+///
+/// # Returns
+///
+/// * `String` - A table with a row per card.
+#[must_use]
+pub fn trace_table(steps: &[Step]) -> String {
+    let mut table = String::from(" Card | Copies | Matches | Won\n------+--------+---------+-----\n");
+
+    for step in steps {
+        let won = step.won.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+        let _ = writeln!(table, "{:>5} | {:>6} | {:>7} | {won}", step.id, step.copies, step.matches);
+    }
+
+    table
+}
+
+/// Render the steps of a simulation as a Graphviz DOT graph.
+///
+/// Every card is a node labelled with its copies, and every edge is labelled with the copies it passed on.
+///
+/// # Arguments
+///
+/// * `steps` - The steps of the simulation.
+///
+/// # Returns
+///
+/// * `String` - A `digraph` with an edge from every card to every card it won copies of.
+#[must_use]
+pub fn trace_dot(steps: &[Step]) -> String {
+    let mut dot = String::from("digraph cascade {\n    rankdir=LR;\n    node [shape=box];\n");
+
+    for step in steps {
+        let _ = writeln!(dot, "    {} [label=\"Card {}\\n{} copies\"];", step.id, step.id, step.copies);
+    }
+    for step in steps {
+        for won in &step.won {
+            let _ = writeln!(dot, "    {} -> {won} [label=\"{}\"];", step.id, step.copies);
+        }
+    }
+    dot.push_str("}\n");
+
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use super::*;
+
+    fn example() -> Result<Vec<Card>> {
+        let input = r"
+            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+            Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+            Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+            Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+            Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+        "
+        .trim();
+
+        Ok(input.lines().map(Card::try_from).collect::<Result<Vec<_>, _>>()?)
+    }
+
+    #[test]
+    fn test_simulate() -> Result<()> {
+        let cards = example()?;
+        let steps = simulate(&cards, OverflowPolicy::Error).collect::<Result<Vec<_>, _>>()?;
+
+        let copies = steps.iter().map(|step| step.copies).collect::<Vec<_>>();
+        assert_eq!(copies, [1, 2, 4, 8, 14, 1]);
+        assert_eq!(copies.iter().sum::<usize>(), 30);
+
+        assert_eq!(steps[0].matches, 4);
+        assert_eq!(steps[0].won, [2, 3, 4, 5]);
+        assert!(steps[5].won.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_overflow() -> Result<()> {
+        let cards = example()?;
+        let cards = &cards[..2];
+
+        let steps = simulate(cards, OverflowPolicy::Error).collect::<Vec<_>>();
+        assert_eq!(steps, [Err(CopiesError::Missing(MissingCardError { id: Some(3), won_by: 1 }))]);
+
+        let steps = simulate(cards, OverflowPolicy::Clamp).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(steps.iter().map(|step| step.copies).collect::<Vec<_>>(), [1, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_large_ids() -> Result<()> {
+        let cards = [format!("Card {}: 1 2 | 1 2", usize::MAX - 1), format!("Card {}: 1 | 1", usize::MAX)]
+            .iter()
+            .map(|line| Card::try_from(line.as_str()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let steps = simulate(&cards, OverflowPolicy::Clamp).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(steps.iter().map(|step| step.copies).collect::<Vec<_>>(), [1, 2]);
+
+        let steps = simulate(&cards, OverflowPolicy::Error).collect::<Vec<_>>();
+        assert_eq!(steps, [Err(CopiesError::Missing(MissingCardError { id: None, won_by: usize::MAX - 1 }))]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_unsorted() -> Result<()> {
+        let mut cards = example()?;
+        cards.swap(0, 1);
+
+        let steps = simulate(&cards, OverflowPolicy::Error).collect::<Vec<_>>();
+        assert_eq!(steps, [Err(CopiesError::Unsorted { id: 1, previous: 2 })]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_copies_overflow() -> Result<()> {
+        // Every card wins a copy of the next two, so the copies grow like the Fibonacci numbers.
+        let cards = (1..=100)
+            .map(|id| Card::try_from(format!("Card {id}: 1 2 | 1 2").as_str()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let error = simulate(&cards, OverflowPolicy::Clamp).find_map(Result::err);
+        assert!(matches!(error, Some(CopiesError::Overflow { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace() -> Result<()> {
+        let cards = example()?;
+        let steps = simulate(&cards, OverflowPolicy::Error).collect::<Result<Vec<_>, _>>()?;
+
+        let table = trace_table(&steps);
+        assert_eq!(table.lines().count(), 8);
+        assert_eq!(table.lines().nth(3), Some("    2 |      2 |       2 | 3, 4"));
+
+        let dot = trace_dot(&steps);
+        assert!(dot.starts_with("digraph cascade {"));
+        assert!(dot.contains("    2 -> 4 [label=\"2\"];"));
+        assert_eq!(dot.matches("->").count(), 4 + 2 + 2 + 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+use crate::{Card, ParseCardError};
+
+/// An anomaly found while validating a list of cards.
+///
+/// # Fields
+///
+/// * `line` - The line (starting at 1) the anomaly was found on.
+/// * `kind` - The anomaly.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub kind: DiagnosticKind,
+}
+
+/// The kinds of anomalies that can be found in a list of cards.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DiagnosticKind {
+    /// The line is not a valid card, this includes duplicate numbers on a card.
+    Parse(ParseCardError),
+    /// The card has the same ID as the card on an earlier line.
+    DuplicateId { id: usize, first_line: usize },
+    /// The card does not have the ID following the one of the previous card.
+    NonContiguousId { expected: usize, found: usize },
+    /// The card follows the card with the largest possible ID, so no ID can follow it.
+    IdOutOfRange { found: usize },
+    /// The card wins copies of cards past the last card in the table.
+    WinsPastEnd { id: usize, matches: usize, last_id: usize },
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+
+        match &self.kind {
+            DiagnosticKind::Parse(error) => write!(f, "{error}"),
+            DiagnosticKind::DuplicateId { id, first_line } => {
+                write!(f, "card {id} was already defined on line {first_line}")
+            }
+            DiagnosticKind::NonContiguousId { expected, found } => {
+                write!(f, "expected card {expected}, found card {found}")
+            }
+            DiagnosticKind::IdOutOfRange { found } => {
+                write!(f, "expected no card after card {}, the largest possible ID, found card {found}", usize::MAX)
+            }
+            DiagnosticKind::WinsPastEnd { id, matches, last_id } => {
+                write!(f, "card {id} has {matches} matches, which wins copies past the last card ({last_id})")
+            }
+        }
+    }
+}
+
+/// Validate a list of cards, one per line, reporting every anomaly instead of stopping at the first.
+///
+/// Blank lines are ignored.
+///
+/// # Arguments
+///
+/// * `input` - The cards to validate.
+///
+/// # Returns
+///
+/// * `Vec<Diagnostic>` - Every anomaly that was found, in the order of the lines.
+///
+/// # Examples
+///
+/// ```
+/// use day_4::{validate, DiagnosticKind};
+///
+/// let input = "Card 1: 41 48 | 41 48\nCard 3: 1 | 1";
+/// let diagnostics = validate(input);
+///
+/// assert_eq!(diagnostics.len(), 2);
+/// assert_eq!(diagnostics[0].kind, DiagnosticKind::NonContiguousId { expected: 2, found: 3 });
+/// assert_eq!(diagnostics[1].kind, DiagnosticKind::WinsPastEnd { id: 3, matches: 1, last_id: 3 });
+/// ```
+#[must_use]
+pub fn validate(input: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut cards = Vec::new();
+    let mut first_lines = HashMap::new();
+    let mut previous_id = None;
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let card = match Card::try_from(line) {
+            Ok(card) => card,
+            Err(error) => {
+                diagnostics.push(Diagnostic { line: line_number, kind: DiagnosticKind::Parse(error) });
+
+                continue;
+            }
+        };
+
+        if let Some(&first_line) = first_lines.get(&card.id) {
+            diagnostics.push(Diagnostic {
+                line: line_number,
+                kind: DiagnosticKind::DuplicateId { id: card.id, first_line },
+            });
+        } else {
+            let kind = match previous_id.map_or(Some(1), |id: usize| id.checked_add(1)) {
+                Some(expected) if card.id == expected => None,
+                Some(expected) => Some(DiagnosticKind::NonContiguousId { expected, found: card.id }),
+                None => Some(DiagnosticKind::IdOutOfRange { found: card.id }),
+            };
+            if let Some(kind) = kind {
+                diagnostics.push(Diagnostic { line: line_number, kind });
+            }
+
+            first_lines.insert(card.id, line_number);
+        }
+
+        previous_id = Some(card.id);
+        cards.push((line_number, card));
+    }
+
+    // Wins can only be checked once the last card is known.
+    let last_id = cards.iter().map(|(_, card)| card.id).max().unwrap_or_default();
+    for (line, card) in &cards {
+        let matches = card.matches();
+        // Copies won past the largest possible ID are past the last card too.
+        if card.id.checked_add(matches).is_none_or(|end| end > last_id) {
+            diagnostics.push(Diagnostic {
+                line: *line,
+                kind: DiagnosticKind::WinsPastEnd { id: card.id, matches, last_id },
+            });
+        }
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ParseCardErrorKind;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_example() {
+        let input = r"
+            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+            Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+            Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+            Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+            Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+        "
+        .trim();
+
+        assert_eq!(validate(input), []);
+    }
+
+    #[test]
+    fn test_validate_anomalies() {
+        let input = r"
+            Card 1: 1 2 | 1
+            Card 2: 3 3 | 4
+
+            Card 1: 5 6 | 7
+            Card 4: 8 9 | 8 9
+        "
+        .trim();
+
+        let kinds = validate(input).into_iter().map(|d| (d.line, d.kind)).collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            [
+                (2, DiagnosticKind::Parse(ParseCardError { column: 23, kind: ParseCardErrorKind::DuplicateNumber(3) })),
+                (4, DiagnosticKind::DuplicateId { id: 1, first_line: 1 }),
+                (5, DiagnosticKind::NonContiguousId { expected: 2, found: 4 }),
+                (5, DiagnosticKind::WinsPastEnd { id: 4, matches: 2, last_id: 4 }),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_validate_large_ids() {
+        let max = usize::MAX;
+        let input = format!("Card {}: 1 | 2\nCard {max}: 1 | 1\nCard 1: 2 | 3", max - 1);
+
+        let kinds = validate(&input).into_iter().map(|d| (d.line, d.kind)).collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            [
+                (1, DiagnosticKind::NonContiguousId { expected: 1, found: max - 1 }),
+                (2, DiagnosticKind::WinsPastEnd { id: max, matches: 1, last_id: max }),
+                (3, DiagnosticKind::IdOutOfRange { found: 1 }),
+            ],
+        );
+    }
+}
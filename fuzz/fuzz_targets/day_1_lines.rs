@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+const WORDS: [&str; 10] = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+fuzz_target!(|input: &str| {
+    let part_1 = day_1::part_1::calibrate_lines(input);
+    let part_2 = day_1::part_2::calibrate_lines(input);
+
+    // Without any spelled-out numbers, both parts find the same digits.
+    if !WORDS.iter().any(|word| input.contains(word)) {
+        assert_eq!(part_1, part_2);
+    }
+});
@@ -0,0 +1,20 @@
+#![no_main]
+
+use day_2::part_1::{are_games_possible, display, parse_game, parse_games};
+use day_2::part_2::{is_game_possible, sum_of_powers};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // Solving the games may overflow, but never panic.
+    if let Ok(games) = parse_games(input) {
+        let _ = are_games_possible(&games);
+        let _ = sum_of_powers(&games.iter().map(is_game_possible).collect::<Vec<_>>());
+    }
+
+    if let Ok(game) = parse_game(input) {
+        let displayed = display(&game).to_string();
+        let reparsed = parse_game(&displayed).unwrap_or_else(|error| panic!("Failed to re-parse `{displayed}`: {error}"));
+
+        assert_eq!(reparsed, game);
+    }
+});
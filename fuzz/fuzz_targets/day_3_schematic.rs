@@ -0,0 +1,19 @@
+#![no_main]
+
+use day_3::Schematic;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(schematic) = input.parse::<Schematic>() {
+        // Solving a schematic may overflow, but never panic.
+        let _ = schematic.sum_of_parts();
+        let _ = schematic.sum_of_gear_ratios();
+
+        let displayed = schematic.to_string();
+        let reparsed = displayed
+            .parse::<Schematic>()
+            .unwrap_or_else(|error| panic!("Failed to re-parse `{displayed}`: {error}"));
+
+        assert_eq!(reparsed, schematic);
+    }
+});
@@ -0,0 +1,25 @@
+#![no_main]
+
+use day_4::{Card, OverflowPolicy};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day_4::validate(input);
+
+    // Solving a table may fail, but never panic. The recursive count of part 2 is exponential, so only the
+    // simulation runs.
+    if let Ok(cards) = day_4::parse_cards(input) {
+        let _ = day_4::sum_points(&cards);
+        for policy in [OverflowPolicy::Error, OverflowPolicy::Clamp] {
+            let _ = day_4::simulate(&cards, policy).collect::<Result<Vec<_>, _>>();
+        }
+    }
+
+    if let Ok(card) = Card::try_from(input) {
+        let displayed = card.to_string();
+        let reparsed =
+            Card::try_from(displayed.as_str()).unwrap_or_else(|error| panic!("Failed to re-parse `{displayed}`: {error}"));
+
+        assert_eq!(reparsed, card);
+    }
+});
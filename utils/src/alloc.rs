@@ -0,0 +1,221 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{self, Display, Formatter};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+/// A global allocator that counts allocations on top of the system allocator.
+///
+/// Installing it is opt-in, every day binary does so behind its `profile-alloc` feature:
+///
+/// ```
+/// #[global_allocator]
+/// static ALLOCATOR: utils::alloc::CountingAllocator = utils::alloc::CountingAllocator;
+/// # fn main() {}
+/// ```
+///
+/// Once installed, every span recorded with [`crate::timing`] carries its [`AllocStats`].
+/// The counters are shared by all threads, so spans are only accurate for single-threaded runs.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_alloc(size: usize) {
+        let size = size as u64;
+
+        INSTALLED.store(true, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        CURRENT.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+
+        new_ptr
+    }
+}
+
+/// Allocation statistics of a span of a run.
+///
+/// # Fields
+///
+/// * `allocations` - The number of allocations, including reallocations.
+/// * `bytes` - The total number of bytes allocated.
+/// * `peak` - The highest number of bytes that were live at once, on top of what was live when the span started.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    pub peak: u64,
+}
+
+impl AllocStats {
+    /// Merge the statistics of another run of the same span into these.
+    pub(crate) fn merge(&mut self, other: Self) {
+        self.allocations += other.allocations;
+        self.bytes += other.bytes;
+        self.peak = self.peak.max(other.peak);
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.allocations,
+            ByteSize(self.bytes),
+            ByteSize(self.peak),
+        )
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.50 KiB`.
+#[derive(Debug, Clone, Copy)]
+pub struct ByteSize(pub u64);
+
+impl Display for ByteSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+
+        write!(f, "{size:.2} {}", UNITS[unit])
+    }
+}
+
+/// The state of the counters when a span was opened.
+#[derive(Debug)]
+pub(crate) struct Snapshot {
+    allocations: u64,
+    bytes: u64,
+    current: u64,
+    outer_peak: u64,
+}
+
+impl Snapshot {
+    /// Take a snapshot of the counters, if the counting allocator is installed.
+    ///
+    /// The peak is reset to the current usage, so it can be read back as the peak of the span.
+    pub(crate) fn take() -> Option<Self> {
+        if !is_installed() {
+            return None;
+        }
+
+        let current = CURRENT.load(Ordering::Relaxed);
+
+        Some(Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+            current,
+            outer_peak: PEAK.swap(current, Ordering::Relaxed),
+        })
+    }
+
+    /// Get the statistics since this snapshot was taken, and restore the peak of the enclosing span.
+    pub(crate) fn finish(self) -> AllocStats {
+        let peak = PEAK.fetch_max(self.outer_peak, Ordering::Relaxed);
+
+        AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+            bytes: BYTES.load(Ordering::Relaxed) - self.bytes,
+            peak: peak.saturating_sub(self.current),
+        }
+    }
+}
+
+/// Whether the [`CountingAllocator`] is installed as the global allocator.
+///
+/// # Returns
+///
+/// * `bool` - Whether any allocation has gone through the counting allocator.
+#[must_use]
+pub fn is_installed() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Get the statistics of the whole run so far.
+///
+/// # Returns
+///
+/// * `Option<AllocStats>` - The statistics, if the counting allocator is installed.
+#[must_use]
+pub fn total() -> Option<AllocStats> {
+    is_installed().then(|| AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak: PEAK.load(Ordering::Relaxed),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_size() {
+        assert_eq!(ByteSize(12).to_string(), "12 B");
+        assert_eq!(ByteSize(1_536).to_string(), "1.50 KiB");
+        assert_eq!(ByteSize(3 * 1024 * 1024).to_string(), "3.00 MiB");
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut stats = AllocStats { allocations: 2, bytes: 64, peak: 48 };
+        stats.merge(AllocStats { allocations: 1, bytes: 16, peak: 16 });
+
+        assert_eq!(stats, AllocStats { allocations: 3, bytes: 80, peak: 48 });
+    }
+}
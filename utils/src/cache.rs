@@ -0,0 +1,304 @@
+//! Snapshots of parsed inputs, so days whose parsing dominates can skip it on later runs.
+//!
+//! A snapshot is the parsed model of an input encoded with `bincode`, after [`MAGIC`], the fingerprint of the crate
+//! that wrote it, the type of the model and the hash of the input it was parsed from. It is only reused by the same
+//! build of the same crate on the same input, and anything else, including a snapshot that no longer decodes,
+//! parses the input again and replaces it. The fingerprint hashes the sources and the enabled features of the crate,
+//! and the sources of `utils`, whose types like `Grid` are part of models too, see [`fingerprint`], so a model whose
+//! fields change, or change with a feature, is never misread.
+//!
+//! The cache is off unless [`enable`] is called, which the days do with `--cache`. Snapshots are written to the
+//! directory in [`DIR_VAR`], or else to `target/aoc-cache` in the workspace.
+
+use std::any::type_name;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::report::hash_input;
+use crate::timing;
+
+/// The bytes every snapshot starts with.
+pub const MAGIC: &[u8] = b"aoc-cache-1\n";
+
+/// The environment variable holding the directory the snapshots are written to.
+pub const DIR_VAR: &str = "AOC_CACHE_DIR";
+
+/// The environment variable a build script passes the fingerprint of its crate in, see [`fingerprint`].
+pub const FINGERPRINT_VAR: &str = "AOC_CACHE_FINGERPRINT";
+
+/// The sources of `utils` itself, which are hashed into every fingerprint.
+const UTILS_SOURCES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn the cache on for the rest of the process.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether the cache is on.
+///
+/// # Returns
+///
+/// * `bool` - Whether [`enable`] was called.
+#[must_use]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// The directory the snapshots are written to, from [`DIR_VAR`] or else `target/aoc-cache` in the workspace.
+///
+/// # Returns
+///
+/// * `PathBuf` - The directory, which may not exist yet.
+#[must_use]
+pub fn directory() -> PathBuf {
+    std::env::var_os(DIR_VAR).map_or_else(
+        || Path::new(env!("CARGO_MANIFEST_DIR")).join("../target/aoc-cache"),
+        PathBuf::from,
+    )
+}
+
+/// Parse an input, or load it from the snapshot of an earlier run if the cache is on, see [`load_or_parse`].
+///
+/// Keys the snapshot by the name and the fingerprint of the calling crate, whose build script must call
+/// [`fingerprint`].
+///
+/// # Examples
+///
+/// ```ignore
+/// fn parse(input: &str) -> Result<Vec<u32>, std::num::ParseIntError> {
+///     input.lines().map(str::parse).collect()
+/// }
+///
+/// let numbers: Result<Vec<u32>, _> = utils::cached!("1\n2", parse);
+///
+/// assert_eq!(numbers, Ok(vec![1, 2]));
+/// ```
+#[macro_export]
+macro_rules! cached {
+    ($input:expr, $parse:expr) => {
+        $crate::cache::load_or_parse(env!("CARGO_PKG_NAME"), env!("AOC_CACHE_FINGERPRINT"), $input, $parse)
+    };
+}
+
+/// Fingerprint a crate for its snapshots, for use in its build script.
+///
+/// The fingerprint hashes the version of the crate, its enabled features and every file in its `src` directory and
+/// in the one of `utils`, whose types the models embed, so any change to its model gives its snapshots a new key, and
+/// is passed to the crate in [`FINGERPRINT_VAR`] for [`cached!`]. Only those two `src` directories are watched, so
+/// other changes do not rerun the build script.
+///
+/// # Returns
+///
+/// * `io::Result<()>` - Nothing.
+///
+/// # Errors
+///
+/// * `io::Error` - If `$CARGO_MANIFEST_DIR` or `$CARGO_PKG_VERSION` is not set, or a source cannot be read.
+pub fn fingerprint() -> io::Result<()> {
+    let var = |var| std::env::var(var).map_err(|_| io::Error::other(format!("{var} is not set")));
+    let sources = Path::new(&var("CARGO_MANIFEST_DIR")?).join("src");
+
+    let mut features = std::env::vars()
+        .filter_map(|(var, _)| var.strip_prefix("CARGO_FEATURE_").map(str::to_lowercase))
+        .collect::<Vec<_>>();
+    features.sort_unstable();
+
+    let hash = hash_sources(&sources, &format!("{} {}", var("CARGO_PKG_VERSION")?, features.join(",")))?;
+    let hash = hash_sources(Path::new(UTILS_SOURCES), &format!("{hash:016x}"))?;
+    println!("cargo::rerun-if-changed={}", sources.display());
+    println!("cargo::rerun-if-changed={UTILS_SOURCES}");
+    println!("cargo::rustc-env={FINGERPRINT_VAR}={hash:016x}");
+
+    Ok(())
+}
+
+/// Hash the path and the contents of every file in a directory and its subdirectories, after a key.
+fn hash_sources(directory: &Path, key: &str) -> io::Result<u64> {
+    let mut files = Vec::new();
+    let mut directories = vec![directory.to_path_buf()];
+    while let Some(next) = directories.pop() {
+        for entry in fs::read_dir(next)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                directories.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files.sort_unstable();
+
+    let mut contents = key.to_string();
+    for file in files {
+        let name = file.strip_prefix(directory).unwrap_or(&file);
+        let _ = write!(contents, "\0{}\0{}", name.display(), String::from_utf8_lossy(&fs::read(&file)?));
+    }
+
+    Ok(hash_input(&contents))
+}
+
+/// Parse an input, or load it from the snapshot of an earlier run if the cache is on.
+///
+/// A missing or stale snapshot is replaced by the freshly parsed input. The cache never fails a run: if a snapshot
+/// cannot be written, a warning is printed and the parsed input is returned all the same.
+///
+/// # Arguments
+///
+/// * `package` - The name of the crate the model belongs to.
+/// * `fingerprint` - The fingerprint of the crate, see [`fingerprint`].
+/// * `input` - The input.
+/// * `parse` - How to parse the input.
+///
+/// # Returns
+///
+/// * `Result<T, E>` - The parsed input.
+///
+/// # Errors
+///
+/// * `E` - If the input has to be parsed and is malformed.
+pub fn load_or_parse<T, E>(
+    package: &str,
+    fingerprint: &str,
+    input: &str,
+    parse: impl FnOnce(&str) -> Result<T, E>,
+) -> Result<T, E>
+where
+    T: Serialize + DeserializeOwned,
+{
+    if !is_enabled() {
+        return parse(input);
+    }
+
+    let hash = hash_input(input);
+    let path = directory().join(format!("{package}-{hash:016x}.bin"));
+
+    if let Some(model) = timing::span("cache load", || load(&path, fingerprint, hash)) {
+        return Ok(model);
+    }
+
+    let model = parse(input)?;
+    if let Err(error) = timing::span("cache store", || store(&path, fingerprint, hash, &model)) {
+        eprintln!("Warning: failed to cache the parsed input in {}: {error}", path.display());
+    }
+
+    Ok(model)
+}
+
+/// Load a snapshot, if it exists and was written by the same build of the crate from the given input.
+///
+/// # Arguments
+///
+/// * `path` - The snapshot.
+/// * `fingerprint` - The fingerprint of the crate that is loading it, see [`fingerprint`].
+/// * `hash` - The hash of the input, see [`hash_input`].
+///
+/// # Returns
+///
+/// * `Option<T>` - The parsed input, or `None` if the snapshot is missing, stale or unreadable.
+#[must_use]
+pub fn load<T: DeserializeOwned>(path: &Path, fingerprint: &str, hash: u64) -> Option<T> {
+    let bytes = fs::read(path).ok()?;
+    let payload = bytes.strip_prefix(MAGIC)?;
+
+    let ((written_by, model_type, input_hash, model), read): ((String, String, u64, T), _) =
+        bincode::serde::decode_from_slice(payload, bincode::config::standard()).ok()?;
+
+    let is_fresh = written_by == fingerprint && model_type == type_name::<T>() && input_hash == hash;
+    (is_fresh && read == payload.len()).then_some(model)
+}
+
+/// Write a snapshot, replacing any earlier one.
+///
+/// # Arguments
+///
+/// * `path` - The snapshot.
+/// * `fingerprint` - The fingerprint of the crate that is writing it, see [`fingerprint`].
+/// * `hash` - The hash of the input, see [`hash_input`].
+/// * `model` - The parsed input.
+///
+/// # Returns
+///
+/// * `io::Result<()>` - Nothing.
+///
+/// # Errors
+///
+/// * `io::Error` - If the model cannot be encoded or the snapshot cannot be written.
+pub fn store<T: Serialize>(path: &Path, fingerprint: &str, hash: u64, model: &T) -> io::Result<()> {
+    let mut bytes = MAGIC.to_vec();
+    let snapshot = (fingerprint, type_name::<T>(), hash, model);
+    bincode::serde::encode_into_std_write(snapshot, &mut bytes, bincode::config::standard()).map_err(io::Error::other)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Write a temporary file and rename it, so a concurrent run never loads a half-written snapshot.
+    let temporary = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&temporary, bytes)?;
+    fs::rename(&temporary, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_store_and_load() -> io::Result<()> {
+        let path = std::env::temp_dir().join(format!("aoc-cache-{}/day_2.bin", std::process::id()));
+        let model = vec![(1_u32, vec![HashMap::from([(String::from("red"), 4_u32)])])];
+
+        assert_eq!(load::<Vec<u32>>(&path, "0.1.0", 7), None);
+
+        store(&path, "0.1.0", 7, &model)?;
+        assert_eq!(load(&path, "0.1.0", 7), Some(model.clone()));
+        assert_eq!(load::<Vec<(u32, Vec<HashMap<String, u32>>)>>(&path, "0.2.0", 7), None);
+        assert_eq!(load::<Vec<(u32, Vec<HashMap<String, u32>>)>>(&path, "0.1.0", 8), None);
+        assert_eq!(load::<Vec<String>>(&path, "0.1.0", 7), None);
+
+        fs::write(&path, b"garbage")?;
+        assert_eq!(load::<Vec<(u32, Vec<HashMap<String, u32>>)>>(&path, "0.1.0", 7), None);
+
+        fs::remove_dir_all(path.parent().unwrap_or(&path))
+    }
+
+    #[test]
+    fn test_hash_sources() -> io::Result<()> {
+        let directory = std::env::temp_dir().join(format!("aoc-sources-{}", std::process::id()));
+        fs::create_dir_all(directory.join("model"))?;
+        fs::write(directory.join("lib.rs"), "mod model;\n")?;
+        fs::write(directory.join("model/mod.rs"), "pub struct Card { pub id: usize }\n")?;
+
+        let hash = hash_sources(&directory, "0.1.0 part_1,part_2")?;
+        assert_eq!(hash_sources(&directory, "0.1.0 part_1,part_2")?, hash);
+        assert_ne!(hash_sources(&directory, "0.1.0 part_1")?, hash);
+
+        fs::write(directory.join("model/mod.rs"), "pub struct Card { pub id: u32 }\n")?;
+        assert_ne!(hash_sources(&directory, "0.1.0 part_1,part_2")?, hash);
+
+        fs::remove_dir_all(&directory)?;
+        assert!(hash_sources(&directory, "").is_err());
+        assert!(Path::new(UTILS_SOURCES).join("cache.rs").is_file());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_or_parse() {
+        let parse = |input: &str| input.split(',').map(str::parse).collect::<Result<Vec<u8>, _>>();
+
+        // The cache is off in tests, so the input is always parsed.
+        assert!(!is_enabled());
+        assert_eq!(load_or_parse("test", "0.1.0", "1,2", parse), Ok(vec![1, 2]));
+        assert!(load_or_parse("test", "0.1.0", "1,x", parse).is_err());
+    }
+}
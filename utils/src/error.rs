@@ -0,0 +1,237 @@
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use color_eyre::eyre;
+
+use crate::parse::ParseError;
+use crate::vault::{self, VaultError};
+
+/// An error that stops a day from producing its answers.
+///
+/// Every variant has its own exit code, see [`Error::exit_code`].
+#[derive(Debug)]
+pub enum Error {
+    /// The puzzle input could not be read.
+    InputMissing { path: PathBuf, source: io::Error },
+    /// The puzzle input is malformed, at the given line and column (both starting at 1).
+    Parse { line: usize, column: usize, message: String },
+    /// The puzzle input is well-formed, but has no answer.
+    Unsolvable(String),
+    /// An answer or an intermediate value does not fit in its type.
+    Overflow(String),
+    /// The puzzle input is encrypted and could not be decrypted.
+    Vault(VaultError),
+}
+
+impl Error {
+    /// The exit code for any error that is not an [`Error`].
+    pub const OTHER_EXIT_CODE: u8 = 1;
+
+    /// Create a parse error.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line (starting at 1) the error occurred on.
+    /// * `column` - The column (starting at 1) the error occurred at.
+    /// * `message` - What went wrong.
+    ///
+    /// # Returns
+    ///
+    /// * `Error` - The parse error.
+    pub fn parse(line: usize, column: usize, message: impl Display) -> Self {
+        Self::Parse {
+            line,
+            column,
+            message: message.to_string(),
+        }
+    }
+
+    /// Get the exit code of the error.
+    ///
+    /// Exit code 2 is left to `clap`, which uses it for invalid arguments.
+    ///
+    /// # Returns
+    ///
+    /// * `u8` - 3 for a missing input, 4 for a parse error, 5 for an unsolvable input, 6 for an overflow and 7 for an
+    ///   input that could not be decrypted.
+    #[must_use]
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::InputMissing { .. } => 3,
+            Self::Parse { .. } => 4,
+            Self::Unsolvable(_) => 5,
+            Self::Overflow(_) => 6,
+            Self::Vault(_) => 7,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InputMissing { path, .. } => write!(f, "could not read the input from {}", path.display()),
+            Self::Parse { line, column, message } => write!(f, "line {line}, column {column}: {message}"),
+            Self::Unsolvable(message) => write!(f, "the input has no answer: {message}"),
+            Self::Overflow(message) => write!(f, "overflow: {message}"),
+            Self::Vault(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InputMissing { source, .. } => Some(source),
+            Self::Vault(error) => error.source(),
+            _ => None,
+        }
+    }
+}
+
+impl From<VaultError> for Error {
+    fn from(error: VaultError) -> Self {
+        Self::Vault(error)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Self::parse(error.line, error.column, error.kind)
+    }
+}
+
+/// Read the puzzle input from a file, or fall back to the input bundled with the binary.
+///
+/// Either input may be encrypted, see [`vault`].
+///
+/// # Arguments
+///
+/// * `path` - The file to read the input from, if any.
+/// * `bundled` - The input bundled with the binary by [`vault::bundle`], empty if there is none.
+///
+/// # Returns
+///
+/// * `Result<Cow<str>, Error>` - The input.
+///
+/// # Errors
+///
+/// * `Error::InputMissing` - If the file cannot be read, or no file is given and no input is bundled.
+/// * `Error::Vault` - If the input is encrypted and cannot be decrypted.
+pub fn read_input(path: Option<&Path>, bundled: &'static [u8]) -> Result<Cow<'static, str>, Error> {
+    let bytes = match path {
+        Some(path) => std::fs::read(path).map(Cow::Owned).map_err(|source| Error::InputMissing {
+            path: path.to_path_buf(),
+            source,
+        })?,
+        None if bundled.is_empty() => {
+            return Err(Error::InputMissing {
+                path: PathBuf::from("input.txt"),
+                source: io::Error::new(io::ErrorKind::NotFound, "no input was bundled, pass one with `--input`"),
+            })
+        }
+        None => Cow::Borrowed(bundled),
+    };
+
+    Ok(vault::open(bytes)?)
+}
+
+/// Get the exit code of an error report, which is that of the first [`Error`] in its chain.
+///
+/// # Arguments
+///
+/// * `report` - The error report.
+///
+/// # Returns
+///
+/// * `u8` - The exit code, or [`Error::OTHER_EXIT_CODE`] if the report does not contain an [`Error`].
+#[must_use]
+pub fn exit_code(report: &eyre::Report) -> u8 {
+    report
+        .chain()
+        .find_map(|error| error.downcast_ref::<Error>())
+        .map_or(Error::OTHER_EXIT_CODE, Error::exit_code)
+}
+
+/// Run the main function of a binary: install the `color_eyre` report handler, then print any error
+/// and exit with its exit code.
+///
+/// # Arguments
+///
+/// * `main` - The actual main function.
+///
+/// # Returns
+///
+/// * `ExitCode` - Success, or the exit code of the error.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::process::ExitCode;
+/// use color_eyre::eyre::Result;
+///
+/// fn main() -> ExitCode {
+///     utils::error::run(solve)
+/// }
+///
+/// fn solve() -> Result<()> {
+///     Ok(())
+/// }
+/// ```
+pub fn run(main: impl FnOnce() -> eyre::Result<()>) -> ExitCode {
+    if let Err(report) = color_eyre::install() {
+        eprintln!("Error: {report:?}");
+
+        return ExitCode::from(Error::OTHER_EXIT_CODE);
+    }
+
+    match main() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(report) => {
+            eprintln!("Error: {report:?}");
+
+            ExitCode::from(exit_code(&report))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::WrapErr;
+
+    use super::*;
+    use crate::parse::{self, Input};
+
+    #[test]
+    fn test_exit_code() {
+        let report = eyre::Report::new(Error::Unsolvable("no gears".to_string()));
+        assert_eq!(exit_code(&report), 5);
+
+        let report = Err::<(), _>(Error::Overflow("too many copies".to_string())).wrap_err("Part 2 failed!");
+        assert_eq!(report.map_err(|report| exit_code(&report)), Err(6));
+
+        assert_eq!(exit_code(&eyre::eyre!("Something else!")), Error::OTHER_EXIT_CODE);
+    }
+
+    #[test]
+    fn test_from_parse_error() {
+        let error = Error::from(parse::tag("Game")(Input::new("Card 1")).unwrap_err());
+
+        assert_eq!(error.exit_code(), 4);
+        assert_eq!(error.to_string(), "line 1, column 1: expected Game");
+    }
+
+    #[test]
+    fn test_read_input() {
+        assert_eq!(read_input(None, b"bundled").ok().as_deref(), Some("bundled"));
+        assert_eq!(read_input(None, b"").map_err(|error| error.exit_code()), Err(3));
+
+        let error = read_input(Some(Path::new("/definitely/not/an/input.txt")), b"bundled").unwrap_err();
+        assert_eq!(error.exit_code(), 3);
+
+        let encrypted = vault::encrypt(&vault::Key::generate(), b"bundled").leak();
+        assert!(matches!(read_input(None, encrypted), Err(Error::Vault(_))));
+    }
+}
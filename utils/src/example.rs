@@ -0,0 +1,14 @@
+/// An example from the puzzle text, with the answer the puzzle gives for it.
+///
+/// Every day keeps its examples in a generated `examples` module, written by `aoc examples` from the puzzle page,
+/// so that its tests and the runner use the examples exactly as they were published.
+///
+/// # Fields
+///
+/// * `input` - The example input.
+/// * `answer` - The answer to the example.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Example {
+    pub input: &'static str,
+    pub answer: &'static str,
+}
@@ -0,0 +1,191 @@
+use std::ops::{Index, IndexMut};
+
+use serde::{Deserialize, Serialize};
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid by calling a function for every cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The number of columns.
+    /// * `height` - The number of rows.
+    /// * `f` - A function from the row and column of a cell to its value.
+    ///
+    /// # Returns
+    ///
+    /// * `Grid<T>` - The grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use utils::grid::Grid;
+    ///
+    /// let grid = Grid::from_fn(3, 2, |row, column| row * 3 + column);
+    ///
+    /// assert_eq!(grid[(1, 2)], 5);
+    /// assert_eq!(grid.get(2, 0), None);
+    /// ```
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |column| (row, column)))
+            .map(|(row, column)| f(row, column))
+            .collect();
+
+        Self { width, height, cells }
+    }
+
+    /// Get the number of columns.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the number of rows.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get a cell, if it is inside the grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row of the cell.
+    /// * `column` - The column of the cell.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&T>` - The cell, if it is inside the grid.
+    #[must_use]
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        (row < self.height && column < self.width).then(|| &self.cells[row * self.width + column])
+    }
+
+    /// Get a mutable cell, if it is inside the grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row of the cell.
+    /// * `column` - The column of the cell.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&mut T>` - The cell, if it is inside the grid.
+    #[must_use]
+    pub fn get_mut(&mut self, row: usize, column: usize) -> Option<&mut T> {
+        (row < self.height && column < self.width).then(|| &mut self.cells[row * self.width + column])
+    }
+
+    /// Iterate over the rows of the grid.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a chunk size of zero, and a grid without columns has no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Map every cell of the grid to a new value.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A function from the row, column and value of a cell to its new value.
+    ///
+    /// # Returns
+    ///
+    /// * `Grid<U>` - A grid of the same size with the new values.
+    pub fn map<U>(&self, mut f: impl FnMut(usize, usize, &T) -> U) -> Grid<U> {
+        Grid::from_fn(self.width, self.height, |row, column| f(row, column, &self[(row, column)]))
+    }
+}
+
+impl Grid<char> {
+    /// Parse a grid of characters, one row per line, ignoring whitespace around every line.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The rows of the grid.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Grid<char>>` - The grid, or `None` if the rows are not all equally wide.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use utils::grid::Grid;
+    ///
+    /// let grid = Grid::parse("ab\ncd").unwrap();
+    ///
+    /// assert_eq!(grid[(1, 0)], 'c');
+    /// assert!(Grid::parse("ab\nc").is_none());
+    /// ```
+    #[must_use]
+    pub fn parse(input: &str) -> Option<Self> {
+        let rows = input.trim().lines().map(|line| line.trim().chars().collect::<Vec<_>>()).collect::<Vec<_>>();
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// Get the cell at the given row and column.
+    ///
+    /// # Panics
+    ///
+    /// * If the cell is outside the grid.
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        self.get(row, column).expect("Cell is outside the grid!")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    /// Get the mutable cell at the given row and column.
+    ///
+    /// # Panics
+    ///
+    /// * If the cell is outside the grid.
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(row, column).expect("Cell is outside the grid!")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let mut grid = Grid::from_fn(2, 3, |row, column| (row, column));
+        grid[(2, 1)] = (9, 9);
+
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid.get(2, 1), Some(&(9, 9)));
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.map(|row, _, _| row).rows().last(), Some(&[2, 2][..]));
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse("  .#.\n  #.#\n").expect("Failed to parse the grid!");
+
+        assert_eq!(grid.rows().map(|row| row.iter().collect::<String>()).collect::<Vec<_>>(), [".#.", "#.#"]);
+        assert_eq!(Grid::parse("").map(|grid| grid.height()), Some(0));
+    }
+}
@@ -1,3 +1,16 @@
+pub mod alloc;
+pub mod cache;
+pub mod error;
+pub mod example;
+pub mod grid;
+pub mod parse;
+pub mod render;
+pub mod report;
+pub mod timing;
+pub mod vault;
+pub mod viz;
+
+mod nanos;
 
 /// Returns the result of the given function and the time it took to execute it.
 ///
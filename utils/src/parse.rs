@@ -0,0 +1,481 @@
+use std::fmt::{self, Display, Formatter};
+
+/// The rest of the input that is being parsed, and where it starts in the full input.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Input<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl<'a> Input<'a> {
+    /// Start parsing the given input.
+    #[inline]
+    #[must_use]
+    pub fn new(source: &'a str) -> Self {
+        Self { source, offset: 0 }
+    }
+
+    /// Get the input that has not been parsed yet.
+    #[inline]
+    #[must_use]
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.offset..]
+    }
+
+    /// Get the byte offset of the rest of the input in the full input.
+    #[inline]
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Whether all of the input has been parsed.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.offset >= self.source.len()
+    }
+
+    /// Get the line and column (both starting at 1) of the rest of the input.
+    #[must_use]
+    pub fn location(&self) -> (usize, usize) {
+        let parsed = &self.source[..self.offset];
+        let line_start = parsed.rfind('\n').map_or(0, |newline| newline + 1);
+
+        (parsed.matches('\n').count() + 1, parsed[line_start..].chars().count() + 1)
+    }
+
+    /// Create an error located at the rest of the input.
+    #[must_use]
+    pub fn error(&self, kind: ErrorKind) -> ParseError {
+        let (line, column) = self.location();
+
+        ParseError { line, column, kind }
+    }
+
+    /// Skip the given number of bytes.
+    #[inline]
+    #[must_use]
+    pub fn advance(&self, bytes: usize) -> Self {
+        Self {
+            source: self.source,
+            offset: (self.offset + bytes).min(self.source.len()),
+        }
+    }
+}
+
+/// The result of a parser: the parsed value and the rest of the input, or an error.
+pub type ParseResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+/// An error that occurred while parsing, and where it occurred.
+///
+/// # Fields
+///
+/// * `line` - The line (starting at 1) the error occurred on.
+/// * `column` - The column (starting at 1) the error occurred at.
+/// * `kind` - What went wrong.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+/// The kinds of errors the parsers in this module produce.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// Something else was expected, e.g. `` `:` `` or `a number`.
+    Expected(&'static str),
+    /// A token is not a number.
+    NotANumber(String),
+    /// A number does not fit in its type.
+    OutOfRange(String),
+    /// There is input left after parsing.
+    Trailing(String),
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Expected(expected) => write!(f, "expected {expected}"),
+            Self::NotANumber(token) => write!(f, "`{token}` is not a number"),
+            Self::OutOfRange(token) => write!(f, "`{token}` is out of range"),
+            Self::Trailing(rest) => write!(f, "unexpected `{rest}`"),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse the whole input, failing if anything is left over.
+///
+/// # Arguments
+///
+/// * `source` - The input to parse.
+/// * `parser` - The parser to parse it with.
+///
+/// # Returns
+///
+/// * `Result<T, ParseError>` - The parsed value.
+///
+/// # Errors
+///
+/// * `ParseError` - If the parser fails, or does not parse all of the input.
+///
+/// # Examples
+///
+/// ```
+/// use utils::parse::{parse_all, separated, uint};
+///
+/// assert_eq!(parse_all("1, 2, 3", separated(uint::<u8>, ", ")), Ok(vec![1, 2, 3]));
+/// assert!(parse_all("1, 2, 3!", separated(uint::<u8>, ", ")).is_err());
+/// ```
+pub fn parse_all<'a, T>(source: &'a str, parser: impl Fn(Input<'a>) -> ParseResult<'a, T>) -> Result<T, ParseError> {
+    let (value, rest) = parser(Input::new(source))?;
+    let ((), _) = end(rest)?;
+
+    Ok(value)
+}
+
+/// Succeed only at the end of the input.
+///
+/// # Errors
+///
+/// * `ParseError` - With [`ErrorKind::Trailing`] if there is input left.
+pub fn end(input: Input<'_>) -> ParseResult<'_, ()> {
+    if input.is_empty() {
+        Ok(((), input))
+    } else {
+        Err(input.error(ErrorKind::Trailing(input.rest().to_string())))
+    }
+}
+
+/// Parse the given literal.
+///
+/// # Arguments
+///
+/// * `literal` - The literal to parse.
+///
+/// # Returns
+///
+/// * `impl Fn(Input) -> ParseResult<&str>` - A parser for the literal.
+pub fn tag<'a>(literal: &'static str) -> impl Fn(Input<'a>) -> ParseResult<'a, &'a str> {
+    move |input| {
+        if input.rest().starts_with(literal) {
+            Ok((&input.rest()[..literal.len()], input.advance(literal.len())))
+        } else {
+            Err(input.error(ErrorKind::Expected(literal)))
+        }
+    }
+}
+
+/// Parse any number of spaces, including none.
+#[inline]
+pub fn spaces(input: Input<'_>) -> ParseResult<'_, &str> {
+    let length = input.rest().bytes().take_while(|&byte| byte == b' ').count();
+
+    Ok((&input.rest()[..length], input.advance(length)))
+}
+
+/// Parse at least one space.
+///
+/// # Errors
+///
+/// * `ParseError` - If there is no space.
+pub fn spaces1(input: Input<'_>) -> ParseResult<'_, &str> {
+    match spaces(input)? {
+        ("", _) => Err(input.error(ErrorKind::Expected("a space"))),
+        parsed => Ok(parsed),
+    }
+}
+
+/// Parse a word of ASCII letters.
+///
+/// # Errors
+///
+/// * `ParseError` - If there is no letter.
+pub fn word(input: Input<'_>) -> ParseResult<'_, &str> {
+    let length = input.rest().len() - input.rest().trim_start_matches(|c: char| c.is_ascii_alphabetic()).len();
+    if length == 0 {
+        return Err(input.error(ErrorKind::Expected("a word")));
+    }
+
+    Ok((&input.rest()[..length], input.advance(length)))
+}
+
+/// Parse an unsigned integer.
+///
+/// The whole token, up to the next ASCII whitespace or punctuation, has to be a number, so `12ab` is an error
+/// instead of `12` followed by `ab`.
+///
+/// # Generics
+///
+/// * `T` - The type of the integer.
+///
+/// # Errors
+///
+/// * `ParseError` - With [`ErrorKind::NotANumber`] or [`ErrorKind::OutOfRange`].
+///
+/// # Examples
+///
+/// ```
+/// use utils::parse::{uint, ErrorKind, Input};
+///
+/// let (number, rest) = uint::<u8>(Input::new("42 red")).unwrap();
+///
+/// assert_eq!(number, 42);
+/// assert_eq!(rest.rest(), " red");
+/// assert_eq!(uint::<u8>(Input::new("256")).unwrap_err().kind, ErrorKind::OutOfRange("256".to_string()));
+/// ```
+#[inline]
+pub fn uint<T: TryFrom<u64>>(input: Input<'_>) -> ParseResult<'_, T> {
+    let rest = input.rest();
+    let bytes = rest.as_bytes();
+    let length = bytes
+        .iter()
+        .position(|byte| byte.is_ascii_whitespace() || byte.is_ascii_punctuation())
+        .unwrap_or(bytes.len());
+
+    if length == 0 {
+        return Err(input.error(ErrorKind::Expected("a number")));
+    }
+
+    // Numbers are parsed in hot loops, so only build the token when there is an error.
+    let mut number = Some(0_u64);
+    for &byte in &bytes[..length] {
+        if !byte.is_ascii_digit() {
+            return Err(input.error(ErrorKind::NotANumber(rest[..length].to_string())));
+        }
+
+        number = number.and_then(|number| number.checked_mul(10)?.checked_add(u64::from(byte - b'0')));
+    }
+
+    match number.and_then(|number| T::try_from(number).ok()) {
+        Some(number) => Ok((number, input.advance(length))),
+        None => Err(input.error(ErrorKind::OutOfRange(rest[..length].to_string()))),
+    }
+}
+
+/// Parse a keyword followed by an ID and a colon, like `Game 12:` or `Card   3:`.
+///
+/// # Arguments
+///
+/// * `keyword` - The keyword, e.g. `Game`.
+///
+/// # Returns
+///
+/// * `impl Fn(Input) -> ParseResult<T>` - A parser for the ID.
+///
+/// # Examples
+///
+/// ```
+/// use utils::parse::{keyword_id, Input};
+///
+/// let (id, rest) = keyword_id::<u32>("Card")(Input::new("Card   3: 41 48")).unwrap();
+///
+/// assert_eq!(id, 3);
+/// assert_eq!(rest.rest(), " 41 48");
+/// ```
+pub fn keyword_id<'a, T: TryFrom<u64>>(keyword: &'static str) -> impl Fn(Input<'a>) -> ParseResult<'a, T> {
+    move |input| {
+        let (_, input) = tag(keyword)(input)?;
+        let (_, input) = spaces1(input)?;
+        let (id, input) = uint(input)?;
+        let (_, input) = tag(":")(input)?;
+
+        Ok((id, input))
+    }
+}
+
+/// Parse one or more items separated by a literal, like `3 blue, 4 red`.
+///
+/// # Arguments
+///
+/// * `item` - The parser for an item.
+/// * `separator` - The literal between the items, e.g. `", "`.
+///
+/// # Returns
+///
+/// * `impl Fn(Input) -> ParseResult<Vec<T>>` - A parser for the list.
+pub fn separated<'a, T>(
+    item: impl Fn(Input<'a>) -> ParseResult<'a, T>,
+    separator: &'static str,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, Vec<T>> {
+    move |input| {
+        let (first, mut input) = item(input)?;
+        let mut items = vec![first];
+
+        // A missing separator ends the list, so check for it directly instead of building an error for it.
+        while input.rest().starts_with(separator) {
+            let (next, rest) = item(input.advance(separator.len()))?;
+            items.push(next);
+            input = rest;
+        }
+
+        Ok((items, input))
+    }
+}
+
+/// Parse any number of items separated by spaces, like ` 41 48  6`, including leading and trailing spaces.
+///
+/// The list ends at the end of the input, at a line break or at punctuation, like the `|` in a card.
+///
+/// # Arguments
+///
+/// * `item` - The parser for an item.
+///
+/// # Returns
+///
+/// * `impl Fn(Input) -> ParseResult<Vec<T>>` - A parser for the list.
+///
+/// # Examples
+///
+/// ```
+/// use utils::parse::{uint, whitespace_list, Input};
+///
+/// let (numbers, rest) = whitespace_list(uint::<u8>)(Input::new(" 41 48  6 | 83")).unwrap();
+///
+/// assert_eq!(numbers, [41, 48, 6]);
+/// assert_eq!(rest.rest(), "| 83");
+/// ```
+pub fn whitespace_list<'a, T>(
+    item: impl Fn(Input<'a>) -> ParseResult<'a, T>,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, Vec<T>> {
+    move |input| {
+        let mut items = Vec::new();
+        let (_, mut input) = spaces(input)?;
+
+        let continues = |byte: &u8| !matches!(byte, b'\n' | b'\r') && !byte.is_ascii_punctuation();
+        while input.rest().as_bytes().first().is_some_and(continues) {
+            let (next, rest) = item(input)?;
+            items.push(next);
+            (_, input) = spaces(rest)?;
+        }
+
+        Ok((items, input))
+    }
+}
+
+/// Parse one of several words, returning the value of the word that matched.
+///
+/// # Arguments
+///
+/// * `words` - The words and their values, which are tried in order.
+///
+/// # Returns
+///
+/// * `impl Fn(Input) -> ParseResult<T>` - A parser for the words.
+///
+/// # Examples
+///
+/// ```
+/// use utils::parse::{one_of, Input};
+///
+/// let (number, _) = one_of(&[("one", 1), ("two", 2)])(Input::new("twone")).unwrap();
+///
+/// assert_eq!(number, 2);
+/// ```
+pub fn one_of<'a, T: Copy>(words: &'static [(&'static str, T)]) -> impl Fn(Input<'a>) -> ParseResult<'a, T> {
+    move |input| {
+        words
+            .iter()
+            .find(|(word, _)| input.rest().starts_with(word))
+            .map(|&(word, value)| (value, input.advance(word.len())))
+            .ok_or_else(|| input.error(ErrorKind::Expected("one of the known words")))
+    }
+}
+
+/// Parse every line of the input with the same parser, skipping blank lines.
+///
+/// # Arguments
+///
+/// * `item` - The parser for a line, which has to parse all of it.
+///
+/// # Returns
+///
+/// * `impl Fn(Input) -> ParseResult<Vec<T>>` - A parser for the lines.
+pub fn lines<'a, T>(item: impl Fn(Input<'a>) -> ParseResult<'a, T>) -> impl Fn(Input<'a>) -> ParseResult<'a, Vec<T>> {
+    move |mut input| {
+        let mut items = Vec::new();
+
+        while !input.is_empty() {
+            let length = input.rest().find('\n').unwrap_or(input.rest().len());
+            let line = input.rest()[..length].trim_end_matches('\r');
+
+            if !line.trim().is_empty() {
+                let (value, rest) = item(input)?;
+                let parsed = rest.offset() - input.offset();
+                if parsed < line.len() {
+                    return Err(rest.error(ErrorKind::Trailing(line[parsed..].to_string())));
+                }
+
+                items.push(value);
+            }
+
+            input = input.advance(length + 1);
+        }
+
+        Ok((items, input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let input = Input::new("ab\ncd\nef").advance(4);
+
+        assert_eq!(input.location(), (2, 2));
+        assert_eq!(input.error(ErrorKind::Expected("x")).to_string(), "line 2, column 2: expected x");
+    }
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(tag("Game")(Input::new("Game 1")).map(|(tag, _)| tag), Ok("Game"));
+        assert!(tag("Game")(Input::new("Card 1")).is_err());
+        assert_eq!(spaces1(Input::new("  x")).map(|(_, rest)| rest.rest()), Ok("x"));
+        assert!(spaces1(Input::new("x")).is_err());
+        assert_eq!(word(Input::new("blue, 4")).map(|(word, _)| word), Ok("blue"));
+        assert_eq!(uint::<u32>(Input::new("12ab")).map_err(|e| e.kind), Err(ErrorKind::NotANumber("12ab".to_string())));
+        assert_eq!(uint::<u32>(Input::new("12;")).map(|(n, _)| n), Ok(12));
+    }
+
+    #[test]
+    fn test_combinators() {
+        let cube = |input| {
+            let (count, input) = uint::<u32>(input)?;
+            let (_, input) = spaces1(input)?;
+            let (color, input) = word(input)?;
+
+            Ok(((count, color), input))
+        };
+        let game = |input| {
+            let (id, input) = keyword_id::<u32>("Game")(input)?;
+            let (_, input) = spaces(input)?;
+            let (rounds, input) = separated(separated(cube, ", "), "; ")(input)?;
+
+            Ok(((id, rounds), input))
+        };
+
+        let games = parse_all("Game 1: 3 blue, 4 red; 2 green\nGame 2: 1 red\n", lines(game));
+        assert_eq!(
+            games,
+            Ok(vec![(1, vec![vec![(3, "blue"), (4, "red")], vec![(2, "green")]]), (2, vec![vec![(1, "red")]])]),
+        );
+
+        let error = parse_all("Game 1: 3 blue\nGame 2: 1 red, x green", lines(game)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 16));
+        assert_eq!(error.kind, ErrorKind::NotANumber("x".to_string()));
+
+        let error = parse_all("Game 1: 3 blue!", lines(game)).unwrap_err();
+        assert_eq!((error.line, error.column, error.kind), (1, 15, ErrorKind::Trailing("!".to_string())));
+    }
+}
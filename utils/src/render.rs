@@ -0,0 +1,175 @@
+use std::fmt::Write;
+use std::io::IsTerminal;
+
+use crate::grid::Grid;
+use crate::viz::Rgb;
+
+/// A terminal colour.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+}
+
+impl Color {
+    /// The ANSI SGR code of the foreground colour.
+    fn code(self) -> u8 {
+        match self {
+            Self::Red => 31,
+            Self::Green => 32,
+            Self::Yellow => 33,
+            Self::Blue => 34,
+            Self::Magenta => 35,
+            Self::Cyan => 36,
+            Self::Gray => 90,
+        }
+    }
+
+    /// The colour as red, green and blue components, for drawing images with [`crate::viz`].
+    #[must_use]
+    pub fn rgb(self) -> Rgb {
+        match self {
+            Self::Red => [205, 49, 49],
+            Self::Green => [13, 188, 121],
+            Self::Yellow => [229, 229, 16],
+            Self::Blue => [36, 114, 200],
+            Self::Magenta => [188, 63, 188],
+            Self::Cyan => [17, 168, 205],
+            Self::Gray => [118, 118, 118],
+        }
+    }
+}
+
+/// How a highlighted cell is drawn.
+///
+/// # Fields
+///
+/// * `color` - The colour of the cell when rendering with ANSI escapes.
+/// * `bold` - Whether the cell is bold when rendering with ANSI escapes.
+/// * `marker` - The character marking the cell when rendering plain text.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Style {
+    pub color: Color,
+    pub bold: bool,
+    pub marker: char,
+}
+
+/// How a grid is rendered.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Mode {
+    /// Highlight cells with ANSI colours.
+    Ansi,
+    /// Mark highlighted cells on a line below every row, for when colours are unavailable.
+    Plain,
+}
+
+impl Mode {
+    /// Pick the mode for standard output: colours on a terminal, unless `NO_COLOR` is set.
+    ///
+    /// # Returns
+    ///
+    /// * `Mode` - The mode to render in.
+    #[must_use]
+    pub fn detect() -> Self {
+        if std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none() {
+            Self::Ansi
+        } else {
+            Self::Plain
+        }
+    }
+}
+
+/// Render a grid of characters, highlighting some of the cells.
+///
+/// # Arguments
+///
+/// * `grid` - The grid to render.
+/// * `mode` - Whether to highlight with colours or with markers.
+/// * `style` - A function from the row and column of a cell to its style, or `None` to leave it as is.
+///
+/// # Returns
+///
+/// * `String` - The rendered grid, with a trailing newline.
+///
+/// # Examples
+///
+/// ```
+/// use utils::grid::Grid;
+/// use utils::render::{render, Color, Mode, Style};
+///
+/// let grid = Grid::parse("a.b").unwrap();
+/// let style = Style { color: Color::Red, bold: false, marker: '^' };
+/// let rendered = render(&grid, Mode::Plain, |_, column| (column == 2).then_some(style));
+///
+/// assert_eq!(rendered, "a.b\n  ^\n");
+/// ```
+pub fn render(grid: &Grid<char>, mode: Mode, style: impl Fn(usize, usize) -> Option<Style>) -> String {
+    let mut output = String::new();
+
+    for (row, cells) in grid.rows().enumerate() {
+        match mode {
+            Mode::Ansi => {
+                for (column, &cell) in cells.iter().enumerate() {
+                    match style(row, column) {
+                        Some(Style { color, bold: true, .. }) => {
+                            let _ = write!(output, "\x1b[1;{}m{cell}\x1b[0m", color.code());
+                        }
+                        Some(Style { color, bold: false, .. }) => {
+                            let _ = write!(output, "\x1b[{}m{cell}\x1b[0m", color.code());
+                        }
+                        None => output.push(cell),
+                    }
+                }
+                output.push('\n');
+            }
+            Mode::Plain => {
+                output.extend(cells);
+                output.push('\n');
+
+                // Only add a line of markers below rows that have highlighted cells.
+                let markers = (0..cells.len())
+                    .map(|column| style(row, column).map_or(' ', |style| style.marker))
+                    .collect::<String>();
+                let markers = markers.trim_end();
+                if !markers.is_empty() {
+                    output.push_str(markers);
+                    output.push('\n');
+                }
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STYLE: Style = Style {
+        color: Color::Green,
+        bold: true,
+        marker: '=',
+    };
+
+    #[test]
+    fn test_render_ansi() {
+        let grid = Grid::parse("ab\ncd").expect("Failed to parse the grid!");
+        let rendered = render(&grid, Mode::Ansi, |row, column| (row == column).then_some(STYLE));
+
+        assert_eq!(rendered, "\x1b[1;32ma\x1b[0mb\nc\x1b[1;32md\x1b[0m\n");
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let grid = Grid::parse("ab\ncd\nef").expect("Failed to parse the grid!");
+        let rendered = render(&grid, Mode::Plain, |row, column| (row == 1 && column == 0).then_some(STYLE));
+
+        assert_eq!(rendered, "ab\ncd\n=\nef\n");
+    }
+}
@@ -0,0 +1,472 @@
+use std::fmt::{self, Display, Formatter, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::alloc::{AllocStats, ByteSize};
+use crate::timing::Timings;
+
+/// The format a report can be rendered in.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Format {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = ParseFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err(ParseFormatError(s.to_string())),
+        }
+    }
+}
+
+/// The error returned when a report format is not recognized.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseFormatError(String);
+
+impl Display for ParseFormatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown report format `{}` (expected `json`, `csv` or `markdown`)", self.0)
+    }
+}
+
+impl std::error::Error for ParseFormatError {}
+
+/// A structured report of a single run of a day.
+///
+/// # Fields
+///
+/// * `year` - The year of the day that was run.
+/// * `day` - The day that was run.
+/// * `input_hash` - The hash of the input, as returned by [`hash_input`], in hexadecimal.
+/// * `parts` - The results of each part.
+/// * `timings` - Every span that was recorded during the run.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    #[serde(default = "first_year")]
+    pub year: u16,
+    pub day: u8,
+    pub input_hash: String,
+    pub parts: Vec<PartReport>,
+    #[serde(default, skip_serializing_if = "Timings::is_empty")]
+    pub timings: Timings,
+}
+
+/// The result of a single part.
+///
+/// # Fields
+///
+/// * `part` - The part that was run.
+/// * `answer` - The answer to the part, empty unless it was solved.
+/// * `parse` - The time it took to parse the input.
+/// * `solve` - The time it took to solve the part, excluding parsing.
+/// * `alloc` - The allocations made while solving the part, if they were counted.
+/// * `status` - Whether the part was solved, or else how it went wrong.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: String,
+    #[serde(rename = "parse_ns", with = "crate::nanos")]
+    pub parse: Duration,
+    #[serde(rename = "solve_ns", with = "crate::nanos")]
+    pub solve: Duration,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+    #[serde(default)]
+    pub status: Status,
+}
+
+impl PartReport {
+    /// Whether the part was solved, so [`PartReport::answer`] is its answer.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the status is [`Status::Solved`].
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        self.status == Status::Solved
+    }
+}
+
+/// How a part went, which reports written before parts had a status were all solved.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The part has an answer.
+    #[default]
+    Solved,
+    /// The part ran out of time.
+    TimedOut,
+    /// The part panicked.
+    Panicked,
+    /// The part returned an error, with its message.
+    Failed(String),
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Solved => write!(f, "solved"),
+            Self::TimedOut => write!(f, "timed out"),
+            Self::Panicked => write!(f, "panicked"),
+            Self::Failed(error) => write!(f, "failed: {error}"),
+        }
+    }
+}
+
+impl Report {
+    /// Create an empty report for the given day and input.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year of the day that is being run.
+    /// * `day` - The day that is being run.
+    /// * `input` - The input the day is being run on.
+    /// * `timings` - The spans recorded while running the day, see [`crate::timing`].
+    ///
+    /// # Returns
+    ///
+    /// * `Report` - A report without any parts.
+    #[must_use]
+    pub fn new(year: u16, day: u8, input: &str, timings: Timings) -> Self {
+        Self {
+            year,
+            day,
+            input_hash: format!("{:016x}", hash_input(input)),
+            parts: Vec::new(),
+            timings,
+        }
+    }
+
+    /// Add the answer to a part to the report.
+    ///
+    /// The parse and solve times are taken from the `parse` and `part N` spans, and are zero if
+    /// the span was not recorded. The allocations are taken from the `part N` span.
+    ///
+    /// # Arguments
+    ///
+    /// * `part` - The part that was solved.
+    /// * `answer` - The answer to the part.
+    pub fn push(&mut self, part: u8, answer: impl ToString) {
+        let span = self.timings.get(&format!("part {part}"));
+
+        self.parts.push(PartReport {
+            part,
+            answer: answer.to_string(),
+            parse: self.timings.duration("parse"),
+            solve: span.map_or(Duration::ZERO, |span| span.duration),
+            alloc: span.and_then(|span| span.alloc),
+            status: Status::Solved,
+        });
+    }
+
+    /// Add a part that ran out of time to the report, without an answer.
+    ///
+    /// # Arguments
+    ///
+    /// * `part` - The part that ran out of time.
+    /// * `budget` - The time the part was given, which is reported as its solve time.
+    pub fn push_timeout(&mut self, part: u8, budget: Duration) {
+        self.push_unsolved(part, budget, Status::TimedOut);
+    }
+
+    /// Add a part that panicked to the report, without an answer.
+    ///
+    /// # Arguments
+    ///
+    /// * `part` - The part that panicked.
+    /// * `elapsed` - How long the part ran before it panicked, which is reported as its solve time.
+    pub fn push_panic(&mut self, part: u8, elapsed: Duration) {
+        self.push_unsolved(part, elapsed, Status::Panicked);
+    }
+
+    /// Add a part that failed to the report, without an answer.
+    ///
+    /// # Arguments
+    ///
+    /// * `part` - The part that failed.
+    /// * `error` - Why it failed.
+    pub fn push_failure(&mut self, part: u8, error: impl ToString) {
+        self.push_unsolved(part, Duration::ZERO, Status::Failed(error.to_string()));
+    }
+
+    fn push_unsolved(&mut self, part: u8, solve: Duration, status: Status) {
+        self.parts.push(PartReport {
+            part,
+            answer: String::new(),
+            parse: Duration::ZERO,
+            solve,
+            alloc: None,
+            status,
+        });
+    }
+
+    /// Render the report in the given format.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The format to render the report in.
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The rendered report.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use utils::report::{Format, Report};
+    /// use utils::timing;
+    ///
+    /// let answer = timing::span("part 1", || 12);
+    ///
+    /// let mut report = Report::new(2023, 1, "1abc2", timing::take());
+    /// report.push(1, answer);
+    ///
+    /// assert!(report.render(Format::Csv).lines().nth(1).is_some_and(|row| row.contains(",2023,1,1,12,0,")));
+    /// ```
+    #[must_use]
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Json => serde_json::to_string_pretty(self).expect("Failed to serialize report!") + "\n",
+            Format::Csv => self.to_csv(),
+            Format::Markdown => self.to_markdown(),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::from("input_hash,year,day,part,answer,parse_ns,solve_ns,allocations,bytes,peak_bytes\n");
+
+        for part in &self.parts {
+            // Answers are numbers in practice, but quote anything that would break the row.
+            let answer = if part.is_solved() { part.answer.clone() } else { part.status.to_string() };
+            let answer = if answer.contains([',', '"', '\n']) {
+                format!("\"{}\"", answer.replace('"', "\"\""))
+            } else {
+                answer
+            };
+
+            // Leave the allocation columns empty if they were not counted.
+            let alloc = part.alloc.map_or_else(
+                || String::from(",,"),
+                |alloc| format!("{},{},{}", alloc.allocations, alloc.bytes, alloc.peak),
+            );
+
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{answer},{},{},{alloc}",
+                self.input_hash,
+                self.year,
+                self.day,
+                part.part,
+                part.parse.as_nanos(),
+                part.solve.as_nanos(),
+            );
+        }
+
+        csv
+    }
+
+    fn to_markdown(&self) -> String {
+        // Only add the allocation columns if they were counted, to keep the table narrow.
+        let counted = self.parts.iter().any(|part| part.alloc.is_some());
+
+        let mut markdown = String::from("| Year | Day | Part | Answer | Parse | Solve |");
+        if counted {
+            markdown.push_str(" Allocations | Allocated | Peak |");
+        }
+        markdown.push_str(" Input |\n|-----:|----:|-----:|-------:|------:|------:|");
+        if counted {
+            markdown.push_str("------------:|----------:|-----:|");
+        }
+        markdown.push_str(":------|\n");
+
+        for part in &self.parts {
+            // A part without an answer shows how it went instead, in italics.
+            let answer = if part.is_solved() { part.answer.clone() } else { format!("*{}*", part.status) };
+            let _ = write!(
+                markdown,
+                "| {} | {} | {} | {} | {:.2?} | {:.2?} |",
+                self.year,
+                self.day,
+                part.part,
+                answer.replace('|', "\\|"),
+                part.parse,
+                part.solve,
+            );
+            if counted {
+                let alloc = part.alloc.unwrap_or_default();
+                let _ = write!(markdown, " {} | {} | {} |", alloc.allocations, ByteSize(alloc.bytes), ByteSize(alloc.peak));
+            }
+            let _ = writeln!(markdown, " `{}` |", self.input_hash);
+        }
+
+        markdown
+    }
+}
+
+/// The year of the reports written before reports had a year.
+fn first_year() -> u16 {
+    2023
+}
+
+/// Hash the given input, so reports from different inputs can be told apart.
+///
+/// This is a 64-bit FNV-1a hash, which is stable across runs, platforms and compiler versions.
+///
+/// # Arguments
+///
+/// * `input` - The input to hash.
+///
+/// # Returns
+///
+/// * `u64` - The hash of the input.
+///
+/// # Examples
+///
+/// ```
+/// use utils::report::hash_input;
+///
+/// assert_eq!(hash_input(""), 0xcbf2_9ce4_8422_2325);
+/// assert_ne!(hash_input("Card 1"), hash_input("Card 2"));
+/// ```
+#[must_use]
+pub fn hash_input(input: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    input
+        .bytes()
+        .fold(OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::timing::Span;
+
+    fn span(name: &str, micros: u64) -> Span {
+        Span {
+            name: name.to_string(),
+            duration: Duration::from_micros(micros),
+            calls: 1,
+            alloc: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn example() -> Report {
+        let timings = Timings(vec![span("parse", 5), span("part 1", 1), span("part 2", 2)]);
+
+        let mut report = Report::new(2023, 2, "Game 1: 3 blue", timings);
+        report.push(1, 8);
+        report.push(2, 2_286);
+
+        report
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("CSV".parse(), Ok(Format::Csv));
+        assert_eq!("md".parse(), Ok(Format::Markdown));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_render_json() {
+        let report = example();
+        let json = report.render(Format::Json);
+
+        assert!(json.contains("\"parse_ns\": 5000"));
+        assert_eq!(serde_json::from_str::<Report>(&json).ok(), Some(report.clone()));
+
+        // Reports written before reports had a year are all from 2023.
+        let json = json.replace("\"year\": 2023,", "");
+        assert_eq!(serde_json::from_str::<Report>(&json).ok(), Some(report));
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let report = example();
+        let csv = report.render(Format::Csv);
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[2], format!("{},2023,2,2,2286,5000,2000,,,", report.input_hash));
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let markdown = example().render(Format::Markdown);
+
+        assert!(markdown.starts_with("| Year | Day | Part | Answer |"));
+        assert!(!markdown.contains("Allocations"));
+        assert_eq!(markdown.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_render_allocations() {
+        let mut timings = Timings(vec![span("part 1", 1)]);
+        timings.0[0].alloc = Some(AllocStats { allocations: 3, bytes: 2_048, peak: 1_024 });
+
+        let mut report = Report::new(2023, 1, "", timings);
+        report.push(1, 12);
+
+        assert!(report.render(Format::Csv).ends_with(",1,12,0,1000,3,2048,1024\n"));
+        assert!(report.render(Format::Markdown).contains("| 3 | 2.00 KiB | 1.00 KiB |"));
+    }
+
+    #[test]
+    fn test_render_timeout() {
+        let mut report = example();
+        report.push_timeout(3, Duration::from_secs(1));
+
+        assert!(report.render(Format::Csv).ends_with(",2023,2,3,timed out,0,1000000000,,,\n"));
+        assert!(report.render(Format::Markdown).contains("| 3 | *timed out* | 0.00ns | 1.00s |"));
+
+        let json = report.render(Format::Json);
+        assert_eq!(json.matches("\"status\": \"timed_out\"").count(), 1);
+        assert_eq!(json.matches("\"status\": \"solved\"").count(), 2);
+        assert_eq!(serde_json::from_str::<Report>(&json).ok(), Some(report));
+    }
+
+    #[test]
+    fn test_render_panic() {
+        let mut report = example();
+        report.push_panic(3, Duration::from_millis(2));
+
+        assert!(!report.parts[2].is_solved() && report.parts[0].is_solved());
+        assert!(report.render(Format::Csv).ends_with(",2023,2,3,panicked,0,2000000,,,\n"));
+        assert!(report.render(Format::Markdown).contains("| 3 | *panicked* | 0.00ns | 2.00ms |"));
+
+        let json = report.render(Format::Json);
+        assert_eq!(json.matches("\"status\": \"panicked\"").count(), 1);
+        assert_eq!(serde_json::from_str::<Report>(&json).ok(), Some(report));
+    }
+
+    #[test]
+    fn test_render_failure() {
+        let mut report = example();
+        report.push_failure(3, "card 3 is missing, so is card 4");
+
+        assert!(report.render(Format::Csv).contains(",3,\"failed: card 3 is missing, so is card 4\",0,0,"));
+        assert!(report.render(Format::Markdown).contains("| 3 | *failed: card 3 is missing, so is card 4* |"));
+
+        let json = report.render(Format::Json);
+        assert!(json.contains("\"failed\": \"card 3 is missing, so is card 4\""));
+        assert_eq!(serde_json::from_str::<Report>(&json).ok(), Some(report));
+
+        let old = json.replace(",\n      \"status\": \"solved\"", "");
+        assert!(!old.contains("\"solved\""));
+        assert_eq!(serde_json::from_str::<Report>(&old).map(|report| report.parts[0].is_solved()).ok(), Some(true));
+    }
+}
@@ -0,0 +1,264 @@
+use std::cell::RefCell;
+use std::fmt::{self, Display, Formatter};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::alloc::{AllocStats, Snapshot};
+
+thread_local! {
+    /// The spans that are currently open on this thread, innermost last.
+    static OPEN: RefCell<Vec<Span>> = const { RefCell::new(Vec::new()) };
+
+    /// The root spans that have finished on this thread.
+    static FINISHED: RefCell<Vec<Span>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A named, timed section of a run, and the spans that were opened inside of it.
+///
+/// # Fields
+///
+/// * `name` - The name of the span.
+/// * `duration` - The total time spent inside the span.
+/// * `calls` - How many times the span was entered, since sibling spans with the same name are merged.
+/// * `alloc` - The allocations made inside the span, if the [`crate::alloc::CountingAllocator`] is installed.
+/// * `children` - The spans that were opened inside of this one.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Span {
+    pub name: String,
+    #[serde(rename = "duration_ns", with = "crate::nanos")]
+    pub duration: Duration,
+    pub calls: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Span>,
+}
+
+impl Span {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            duration: Duration::ZERO,
+            calls: 1,
+            alloc: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Merge another span with the same name into this one.
+    fn merge(&mut self, other: Self) {
+        self.duration += other.duration;
+        self.calls += other.calls;
+
+        match (&mut self.alloc, other.alloc) {
+            (Some(alloc), Some(other)) => alloc.merge(other),
+            (alloc @ None, other) => *alloc = other,
+            (Some(_), None) => {}
+        }
+
+        for child in other.children {
+            insert(&mut self.children, child);
+        }
+    }
+}
+
+/// Insert a span into a list of siblings, merging it with an existing span of the same name.
+fn insert(siblings: &mut Vec<Span>, span: Span) {
+    match siblings.iter_mut().find(|sibling| sibling.name == span.name) {
+        Some(sibling) => sibling.merge(span),
+        None => siblings.push(span),
+    }
+}
+
+/// Closes the innermost open span when dropped, even if the timed closure panics.
+struct Guard {
+    start: Instant,
+    snapshot: Option<Snapshot>,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        let alloc = self.snapshot.take().map(Snapshot::finish);
+
+        let Some(mut span) = OPEN.with_borrow_mut(Vec::pop) else {
+            return;
+        };
+        span.duration = elapsed;
+        span.alloc = alloc;
+
+        OPEN.with_borrow_mut(|open| match open.last_mut() {
+            Some(parent) => insert(&mut parent.children, span),
+            None => FINISHED.with_borrow_mut(|finished| insert(finished, span)),
+        });
+    }
+}
+
+/// Run the given function inside of a named span.
+///
+/// Spans opened while the function runs become children of this span, so time is attributed to
+/// every level of the run. Sibling spans with the same name are merged, so a span opened in a loop
+/// shows up once with its total time.
+///
+/// # Arguments
+///
+/// * `name` - The name of the span, e.g. `parse` or `part 1`.
+/// * `f` - The function to execute.
+///
+/// # Returns
+///
+/// * `T` - The result of the given function.
+///
+/// # Examples
+///
+/// ```
+/// use utils::timing;
+///
+/// let sum = timing::span("part 1", || {
+///     let numbers = timing::span("parse", || vec![1, 2, 3]);
+///
+///     timing::span("sum", || numbers.iter().sum::<u32>())
+/// });
+/// let timings = timing::take();
+///
+/// assert_eq!(sum, 6);
+/// assert_eq!(timings.get("part 1/parse").map(|span| span.calls), Some(1));
+/// ```
+pub fn span<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    OPEN.with_borrow_mut(|open| open.push(Span::new(name)));
+
+    let _guard = Guard {
+        snapshot: Snapshot::take(),
+        start: Instant::now(),
+    };
+
+    f()
+}
+
+/// Take every finished root span recorded on this thread, leaving none behind.
+///
+/// # Returns
+///
+/// * `Timings` - The finished spans.
+#[must_use]
+pub fn take() -> Timings {
+    Timings(FINISHED.with_borrow_mut(std::mem::take))
+}
+
+/// A tree of finished spans.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Timings(pub Vec<Span>);
+
+impl Timings {
+    /// Find a span by its path, where the names of nested spans are separated by `/`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the span, e.g. `part 2/bags`.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Span>` - The span, if it was recorded.
+    #[must_use]
+    pub fn get(&self, path: &str) -> Option<&Span> {
+        let mut names = path.split('/');
+        let first = names.next()?;
+        let mut span = self.0.iter().find(|span| span.name == first)?;
+
+        for name in names {
+            span = span.children.iter().find(|span| span.name == name)?;
+        }
+
+        Some(span)
+    }
+
+    /// Get the time spent in a span, or zero if it was never recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the span, e.g. `part 2/bags`.
+    ///
+    /// # Returns
+    ///
+    /// * `Duration` - The time spent in the span.
+    #[must_use]
+    pub fn duration(&self, path: &str) -> Duration {
+        self.get(path).map_or(Duration::ZERO, |span| span.duration)
+    }
+
+    /// Whether no spans were recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fn write_span(f: &mut Formatter<'_>, span: &Span, depth: usize) -> fmt::Result {
+            write!(f, "{:indent$}{}: {:.2?}", "", span.name, span.duration, indent = depth * 2)?;
+            if span.calls > 1 {
+                write!(f, " ({} calls)", span.calls)?;
+            }
+            if let Some(alloc) = span.alloc {
+                write!(f, " [{alloc}]")?;
+            }
+            writeln!(f)?;
+
+            span.children
+                .iter()
+                .try_for_each(|child| write_span(f, child, depth + 1))
+        }
+
+        self.0.iter().try_for_each(|span| write_span(f, span, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_spans() {
+        span("parse", || {});
+        span("part 1", || {
+            span("bags", || {});
+            span("power", || {});
+        });
+        let timings = take();
+
+        let names = timings.0.iter().map(|span| span.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["parse", "part 1"]);
+        assert_eq!(timings.get("part 1").map(|span| span.children.len()), Some(2));
+        assert!(timings.get("part 1/power").is_some());
+        assert!(timings.get("parse/power").is_none());
+
+        assert!(take().is_empty());
+    }
+
+    #[test]
+    fn test_repeated_spans_are_merged() {
+        span("part 2", || {
+            for _ in 0..3 {
+                span("step", || {});
+            }
+        });
+        let timings = take();
+
+        assert_eq!(timings.get("part 2/step").map(|span| span.calls), Some(3));
+        assert!(timings.duration("part 2") >= timings.duration("part 2/step"));
+    }
+
+    #[test]
+    fn test_display() {
+        span("parse", || span("line", || {}));
+        let report = take().to_string();
+        let lines = report.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("parse: "));
+        assert!(lines[1].starts_with("  line: "));
+    }
+}
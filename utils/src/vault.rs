@@ -0,0 +1,388 @@
+//! Puzzle inputs encrypted at rest, so they can be committed without publishing them.
+//!
+//! An encrypted input starts with [`MAGIC`], followed by a random nonce and the input sealed with
+//! ChaCha20-Poly1305. The key is 32 bytes written as 64 hexadecimal digits, read from the
+//! [`KEY_VAR`] environment variable, from the file named by [`KEY_FILE_VAR`], or from
+//! `~/.config/aoc/input.key`, in that order.
+//!
+//! Every day bundles its input with a build script calling [`bundle`], which prefers a plain `input.txt`
+//! and falls back to the encrypted `input.txt.enc`, and opens it at run time with [`open`].
+
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+
+/// The bytes every encrypted input starts with.
+pub const MAGIC: &[u8] = b"aoc-vault-1\n";
+
+/// The extension added to the name of an encrypted input, e.g. `input.txt.enc`.
+pub const EXTENSION: &str = "enc";
+
+/// The environment variable holding the key.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+/// The environment variable holding the path of the file holding the key.
+pub const KEY_FILE_VAR: &str = "AOC_INPUT_KEY_FILE";
+
+const NONCE_LENGTH: usize = 12;
+
+/// A key to encrypt and decrypt inputs with.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Key([u8; 32]);
+
+impl Key {
+    /// Generate a new random key.
+    ///
+    /// # Returns
+    ///
+    /// * `Key` - The key.
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(ChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    /// Parse a key from its 64 hexadecimal digits, ignoring surrounding whitespace.
+    ///
+    /// # Arguments
+    ///
+    /// * `hex` - The digits of the key.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Key, VaultError>` - The key.
+    ///
+    /// # Errors
+    ///
+    /// * `VaultError::InvalidKey` - If the key is not 64 hexadecimal digits.
+    pub fn from_hex(hex: &str) -> Result<Self, VaultError> {
+        let hex = hex.trim().as_bytes();
+        if hex.len() != 64 {
+            return Err(VaultError::InvalidKey(format!("expected 64 hexadecimal digits, found {}", hex.len())));
+        }
+
+        let mut key = [0; 32];
+        for (byte, digits) in key.iter_mut().zip(hex.chunks(2)) {
+            let digits = std::str::from_utf8(digits).ok().and_then(|digits| u8::from_str_radix(digits, 16).ok());
+            *byte = digits.ok_or_else(|| VaultError::InvalidKey("expected only hexadecimal digits".to_string()))?;
+        }
+
+        Ok(Self(key))
+    }
+
+    /// Write the key as 64 hexadecimal digits, the format read by [`Key::from_hex`].
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The digits of the key.
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// Read a key from a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file holding the key.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Key, VaultError>` - The key.
+    ///
+    /// # Errors
+    ///
+    /// * `VaultError::Io` - If the file cannot be read.
+    /// * `VaultError::InvalidKey` - If the file does not hold a key.
+    pub fn read(path: &Path) -> Result<Self, VaultError> {
+        let hex = std::fs::read_to_string(path).map_err(|source| VaultError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        Self::from_hex(&hex)
+    }
+
+    /// Load the key from [`KEY_VAR`], the file named by [`KEY_FILE_VAR`] or the default key file.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Key, VaultError>` - The key.
+    ///
+    /// # Errors
+    ///
+    /// * `VaultError::NoKey` - If none of them is set.
+    /// * `VaultError::Io` - If the key file cannot be read.
+    /// * `VaultError::InvalidKey` - If the key is malformed.
+    pub fn load() -> Result<Self, VaultError> {
+        if let Ok(hex) = std::env::var(KEY_VAR) {
+            return Self::from_hex(&hex);
+        }
+
+        if let Some(path) = std::env::var_os(KEY_FILE_VAR) {
+            return Self::read(Path::new(&path));
+        }
+
+        match default_key_path() {
+            Some(path) if path.exists() => Self::read(&path),
+            _ => Err(VaultError::NoKey),
+        }
+    }
+}
+
+impl Debug for Key {
+    /// Never print the key itself, e.g. in an error report.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Key(..)")
+    }
+}
+
+/// An error while encrypting or decrypting an input.
+#[derive(Debug)]
+pub enum VaultError {
+    /// The input is encrypted, but no key is configured.
+    NoKey,
+    /// The configured key is malformed.
+    InvalidKey(String),
+    /// A key file or an input could not be read or written.
+    Io { path: PathBuf, source: io::Error },
+    /// The input is encrypted, but too short to hold a nonce.
+    Truncated,
+    /// The input could not be decrypted, because the key is wrong or the input was modified.
+    WrongKey,
+    /// The decrypted input is not UTF-8.
+    NotUtf8,
+}
+
+impl Display for VaultError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoKey => write!(f, "the input is encrypted, but there is no key in {KEY_VAR}, {KEY_FILE_VAR} or {DEFAULT_KEY_FILE}"),
+            Self::InvalidKey(message) => write!(f, "invalid input key: {message}"),
+            Self::Io { path, .. } => write!(f, "could not access {}", path.display()),
+            Self::Truncated => f.write_str("the encrypted input is truncated"),
+            Self::WrongKey => f.write_str("could not decrypt the input, the key is wrong or the input was modified"),
+            Self::NotUtf8 => f.write_str("the decrypted input is not UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for VaultError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// The default key file, relative to the home directory.
+const DEFAULT_KEY_FILE: &str = "~/.config/aoc/input.key";
+
+/// The default key file, `~/.config/aoc/input.key`.
+///
+/// # Returns
+///
+/// * `Option<PathBuf>` - The path, or `None` if there is no home directory.
+#[must_use]
+pub fn default_key_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| Path::new(&home).join(&DEFAULT_KEY_FILE[2..]))
+}
+
+/// Check whether bytes are an encrypted input.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to check.
+///
+/// # Returns
+///
+/// * `bool` - Whether the bytes start with [`MAGIC`].
+#[must_use]
+pub fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Encrypt an input with a fresh random nonce.
+///
+/// # Arguments
+///
+/// * `key` - The key to encrypt with.
+/// * `plaintext` - The input.
+///
+/// # Returns
+///
+/// * `Vec<u8>` - The encrypted input, starting with [`MAGIC`].
+#[must_use]
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Vec<u8> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(&key.0.into())
+        .encrypt(&nonce, plaintext)
+        .expect("Encrypting in memory cannot fail!");
+
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+/// Decrypt an input encrypted by [`encrypt`].
+///
+/// # Arguments
+///
+/// * `key` - The key the input was encrypted with.
+/// * `bytes` - The encrypted input, with or without [`MAGIC`].
+///
+/// # Returns
+///
+/// * `Result<Vec<u8>, VaultError>` - The input.
+///
+/// # Errors
+///
+/// * `VaultError::Truncated` - If the input is too short to hold a nonce.
+/// * `VaultError::WrongKey` - If the key is wrong or the input was modified.
+pub fn decrypt(key: &Key, bytes: &[u8]) -> Result<Vec<u8>, VaultError> {
+    let bytes = bytes.strip_prefix(MAGIC).unwrap_or(bytes);
+    if bytes.len() < NONCE_LENGTH {
+        return Err(VaultError::Truncated);
+    }
+
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LENGTH);
+    ChaCha20Poly1305::new(&key.0.into())
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| VaultError::WrongKey)
+}
+
+/// Open an input, decrypting it with the key from [`Key::load`] if it is encrypted.
+///
+/// # Arguments
+///
+/// * `bytes` - The input, encrypted or not.
+///
+/// # Returns
+///
+/// * `Result<Cow<str>, VaultError>` - The input, borrowed if it was not encrypted.
+///
+/// # Errors
+///
+/// * `VaultError` - If the input is encrypted and cannot be decrypted, or is not UTF-8.
+pub fn open(bytes: Cow<'_, [u8]>) -> Result<Cow<'_, str>, VaultError> {
+    if !is_encrypted(&bytes) {
+        return match bytes {
+            Cow::Borrowed(bytes) => std::str::from_utf8(bytes).map(Cow::Borrowed).map_err(|_| VaultError::NotUtf8),
+            Cow::Owned(bytes) => String::from_utf8(bytes).map(Cow::Owned).map_err(|_| VaultError::NotUtf8),
+        };
+    }
+
+    let plaintext = decrypt(&Key::load()?, &bytes)?;
+
+    String::from_utf8(plaintext).map(Cow::Owned).map_err(|_| VaultError::NotUtf8)
+}
+
+/// Include the input bundled by [`bundle`] as a `&'static [u8]`, to pass to [`crate::error::read_input`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let input = utils::error::read_input(args.input.as_deref(), utils::bundled_input!())?;
+/// ```
+#[macro_export]
+macro_rules! bundled_input {
+    () => {
+        include_bytes!(concat!(env!("OUT_DIR"), "/input.txt"))
+    };
+}
+
+/// Bundle an input with a crate, for use in its build script.
+///
+/// Copies the plain input if it exists, or else the encrypted one next to it, to `$OUT_DIR` under the plain name,
+/// so it can be included with [`bundled_input!`] and read with [`open`].
+/// If neither exists, an empty file is bundled and the build prints a warning.
+///
+/// Missing files are not watched, since Cargo reruns the build script every time if a watched file is missing.
+/// Instead, the whole crate is watched whenever the plain input is missing, so it is bundled in place of the
+/// encrypted one, or of nothing, as soon as it is added, e.g. by `aoc vault decrypt`.
+///
+/// # Arguments
+///
+/// * `name` - The name of the plain input, relative to the crate, e.g. `input.txt`.
+///
+/// # Returns
+///
+/// * `io::Result<()>` - Nothing.
+///
+/// # Errors
+///
+/// * `io::Error` - If `$OUT_DIR` or `$CARGO_MANIFEST_DIR` is not set, or a file cannot be copied.
+pub fn bundle(name: &str) -> io::Result<()> {
+    let directory = |var| {
+        std::env::var_os(var)
+            .map(PathBuf::from)
+            .ok_or_else(|| io::Error::other(format!("{var} is not set")))
+    };
+    let (manifest, out) = (directory("CARGO_MANIFEST_DIR")?, directory("OUT_DIR")?);
+
+    let plain = manifest.join(name);
+    let encrypted = manifest.join(format!("{name}.{EXTENSION}"));
+
+    let bundled = out.join(name);
+    if plain.exists() {
+        println!("cargo::rerun-if-changed={}", plain.display());
+        std::fs::copy(plain, bundled)?;
+    } else if encrypted.exists() {
+        println!("cargo::rerun-if-changed={}", encrypted.display());
+        println!("cargo::rerun-if-changed={}", manifest.display());
+        std::fs::copy(encrypted, bundled)?;
+    } else {
+        println!("cargo::rerun-if-changed={}", manifest.display());
+        println!("cargo::warning=neither {name} nor {name}.{EXTENSION} exists, so no input is bundled");
+        std::fs::write(bundled, "")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_hex() {
+        let key = Key::generate();
+
+        assert_eq!(Key::from_hex(&format!(" {}\n", key.to_hex())).ok(), Some(key));
+        assert!(matches!(Key::from_hex("abc"), Err(VaultError::InvalidKey(_))));
+        assert!(matches!(Key::from_hex(&"g".repeat(64)), Err(VaultError::InvalidKey(_))));
+        assert_eq!(format!("{:?}", Key::generate()), "Key(..)");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let key = Key::generate();
+        let encrypted = encrypt(&key, b"Card 1: 41 48 | 83 86");
+
+        assert!(is_encrypted(&encrypted));
+        assert_ne!(encrypted, encrypt(&key, b"Card 1: 41 48 | 83 86"));
+        assert_eq!(decrypt(&key, &encrypted).ok().as_deref(), Some(&b"Card 1: 41 48 | 83 86"[..]));
+    }
+
+    #[test]
+    fn test_decrypt_errors() {
+        let key = Key::generate();
+        let mut encrypted = encrypt(&key, b"Game 1: 3 blue");
+
+        assert!(matches!(decrypt(&Key::generate(), &encrypted), Err(VaultError::WrongKey)));
+        assert!(matches!(decrypt(&key, MAGIC), Err(VaultError::Truncated)));
+
+        if let Some(byte) = encrypted.last_mut() {
+            *byte ^= 1;
+        }
+        assert!(matches!(decrypt(&key, &encrypted), Err(VaultError::WrongKey)));
+    }
+
+    #[test]
+    fn test_open_plain() {
+        assert_eq!(open(Cow::Borrowed(b"467..114..")).ok(), Some(Cow::Borrowed("467..114..")));
+        assert!(matches!(open(Cow::Borrowed(&[0xff, 0xfe])), Err(VaultError::NotUtf8)));
+    }
+}
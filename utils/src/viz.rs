@@ -0,0 +1,301 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::grid::Grid;
+
+/// A colour as red, green and blue components.
+pub type Rgb = [u8; 3];
+
+/// An RGB image.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Image {
+    pixels: Grid<Rgb>,
+}
+
+/// The file formats an image can be written in.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ImageFormat {
+    /// A binary (`P6`) portable pixmap, which needs no dependencies.
+    Ppm,
+    /// A PNG, which needs the `png` feature.
+    Png,
+}
+
+impl ImageFormat {
+    /// Pick the format from the extension of a path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the image.
+    ///
+    /// # Returns
+    ///
+    /// * `io::Result<ImageFormat>` - PPM for a `.ppm` extension, PNG for a `.png` extension, in any case.
+    ///
+    /// # Errors
+    ///
+    /// * `io::Error` - If the path has any other extension or none, so an image is never saved in a format its
+    ///   name does not say.
+    pub fn from_path(path: &Path) -> io::Result<Self> {
+        path.extension()
+            .and_then(|extension| extension.to_str()?.parse().ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Cannot tell the image format of {} (expected `.ppm` or `.png`)!", path.display()),
+                )
+            })
+    }
+
+    /// The file extension of the format.
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ppm => "ppm",
+            Self::Png => "png",
+        }
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ppm" => Ok(Self::Ppm),
+            "png" => Ok(Self::Png),
+            _ => Err(format!("unknown image format `{s}` (expected `ppm` or `png`)")),
+        }
+    }
+}
+
+impl Image {
+    /// Draw a grid as an image, with a square of pixels per cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - The grid to draw.
+    /// * `scale` - The width and height of every cell, in pixels.
+    /// * `palette` - A function from the row, column and value of a cell to its colour.
+    ///
+    /// # Returns
+    ///
+    /// * `Image` - The image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use utils::grid::Grid;
+    /// use utils::viz::Image;
+    ///
+    /// let grid = Grid::parse("#.").unwrap();
+    /// let image = Image::from_grid(&grid, 2, |_, _, &cell| if cell == '#' { [0, 0, 0] } else { [255, 255, 255] });
+    ///
+    /// assert_eq!((image.width(), image.height()), (4, 2));
+    ///
+    /// let mut ppm = Vec::new();
+    /// image.write_ppm(&mut ppm).unwrap();
+    ///
+    /// assert!(ppm.starts_with(b"P6\n4 2\n255\n"));
+    /// ```
+    pub fn from_grid<T>(grid: &Grid<T>, scale: usize, palette: impl Fn(usize, usize, &T) -> Rgb) -> Self {
+        let colors = grid.map(palette);
+        let scale = scale.max(1);
+
+        Self {
+            pixels: Grid::from_fn(grid.width() * scale, grid.height() * scale, |y, x| colors[(y / scale, x / scale)]),
+        }
+    }
+
+    /// Get the width of the image, in pixels.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.pixels.width()
+    }
+
+    /// Get the height of the image, in pixels.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.pixels.height()
+    }
+
+    /// Write the image as a binary PPM.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the image to.
+    ///
+    /// # Errors
+    ///
+    /// * `io::Error` - If writing fails.
+    pub fn write_ppm(&self, mut writer: impl Write) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width(), self.height())?;
+        for row in self.pixels.rows() {
+            writer.write_all(row.as_flattened())?;
+        }
+
+        writer.flush()
+    }
+
+    /// Write the image as a PNG.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the image to.
+    ///
+    /// # Errors
+    ///
+    /// * `io::Error` - If writing or encoding fails.
+    #[cfg(feature = "png")]
+    pub fn write_png(&self, writer: impl Write) -> io::Result<()> {
+        let too_large = |_| io::Error::new(io::ErrorKind::InvalidInput, "The image is too large for a PNG!");
+
+        let mut encoder = png::Encoder::new(
+            writer,
+            u32::try_from(self.width()).map_err(too_large)?,
+            u32::try_from(self.height()).map_err(too_large)?,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        let data = self.pixels.rows().flat_map(<[Rgb]>::as_flattened).copied().collect::<Vec<_>>();
+        writer.write_image_data(&data).map_err(io::Error::other)?;
+
+        writer.finish().map_err(io::Error::other)
+    }
+
+    /// Save the image to a file, in the given format.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to save the image to.
+    /// * `format` - The format to save the image in.
+    ///
+    /// # Errors
+    ///
+    /// * `io::Error` - If writing fails, or the format is PNG without the `png` feature.
+    pub fn save(&self, path: &Path, format: ImageFormat) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+
+        match format {
+            ImageFormat::Ppm => self.write_ppm(writer),
+            #[cfg(feature = "png")]
+            ImageFormat::Png => self.write_png(writer),
+            #[cfg(not(feature = "png"))]
+            ImageFormat::Png => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "PNG support requires the `png` feature!",
+            )),
+        }
+    }
+}
+
+/// Writes a numbered sequence of images to a directory, e.g. `frame_0000.ppm`, `frame_0001.ppm`, ...
+#[derive(Debug)]
+pub struct Frames {
+    directory: PathBuf,
+    format: ImageFormat,
+    count: usize,
+}
+
+impl Frames {
+    /// Create the directory for a sequence of frames.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory to write the frames to.
+    /// * `format` - The format to write the frames in.
+    ///
+    /// # Errors
+    ///
+    /// * `io::Error` - If the directory cannot be created.
+    pub fn create(directory: impl Into<PathBuf>, format: ImageFormat) -> io::Result<Self> {
+        let directory = directory.into();
+        std::fs::create_dir_all(&directory)?;
+
+        Ok(Self {
+            directory,
+            format,
+            count: 0,
+        })
+    }
+
+    /// Write the next frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The frame.
+    ///
+    /// # Returns
+    ///
+    /// * `PathBuf` - The path the frame was written to.
+    ///
+    /// # Errors
+    ///
+    /// * `io::Error` - If the frame cannot be written.
+    pub fn push(&mut self, image: &Image) -> io::Result<PathBuf> {
+        let path = self.directory.join(format!("frame_{:04}.{}", self.count, self.format.extension()));
+        image.save(&path, self.format)?;
+        self.count += 1;
+
+        Ok(path)
+    }
+
+    /// Get the number of frames written so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Whether no frames have been written yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_ppm() -> io::Result<()> {
+        let grid = Grid::from_fn(2, 1, |_, column| column);
+        let image = Image::from_grid(&grid, 1, |_, _, &cell| if cell == 0 { [1, 2, 3] } else { [4, 5, 6] });
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm)?;
+
+        assert_eq!(ppm, b"P6\n2 1\n255\n\x01\x02\x03\x04\x05\x06");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(ImageFormat::from_path(Path::new("board.PNG")).ok(), Some(ImageFormat::Png));
+        assert_eq!(ImageFormat::from_path(Path::new("board.ppm")).ok(), Some(ImageFormat::Ppm));
+        let error = ImageFormat::from_path(Path::new("board.jpg")).err().map(|error| error.to_string());
+        assert!(error.is_some_and(|error| error.contains("`.ppm` or `.png`")));
+        assert!(ImageFormat::from_path(Path::new("board")).is_err());
+    }
+
+    #[test]
+    fn test_frames() -> io::Result<()> {
+        let directory = std::env::temp_dir().join(format!("utils-viz-{}", std::process::id()));
+        let image = Image::from_grid(&Grid::from_fn(1, 1, |_, _| ()), 3, |_, _, ()| [0, 0, 0]);
+
+        let mut frames = Frames::create(&directory, ImageFormat::Ppm)?;
+        frames.push(&image)?;
+        let last = frames.push(&image)?;
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(last.file_name().and_then(|name| name.to_str()), Some("frame_0001.ppm"));
+        assert_eq!(std::fs::read(&last)?.len(), b"P6\n3 3\n255\n".len() + 27);
+
+        std::fs::remove_dir_all(directory)
+    }
+}
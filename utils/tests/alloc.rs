@@ -0,0 +1,27 @@
+use utils::alloc::CountingAllocator;
+use utils::timing;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_spans_count_allocations() {
+    let total = timing::span("outer", || {
+        let numbers = timing::span("inner", || (0..1_024_u64).collect::<Vec<_>>());
+
+        numbers.iter().sum::<u64>()
+    });
+    let timings = timing::take();
+
+    assert_eq!(total, 523_776);
+
+    let outer = timings.get("outer").and_then(|span| span.alloc).expect("No stats for the outer span!");
+    let inner = timings.get("outer/inner").and_then(|span| span.alloc).expect("No stats for the inner span!");
+
+    assert!(inner.allocations >= 1);
+    assert!(inner.bytes >= 1_024 * 8);
+    assert!(inner.peak >= 1_024 * 8);
+    assert!(outer.allocations >= inner.allocations);
+    assert!(outer.peak >= inner.peak);
+    assert!(utils::alloc::total().is_some());
+}